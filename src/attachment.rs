@@ -0,0 +1,126 @@
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+
+use crate::{handle_response, Yuque, YuqueError, YuqueResponse};
+
+/// 上传后的附件，只取用得到的链接
+#[derive(Deserialize, Debug)]
+struct UploadedAttachment {
+    url: String,
+}
+
+#[derive(Debug)]
+pub struct AttachmentsClient {
+    pub(crate) client: Yuque,
+}
+
+impl AttachmentsClient {
+    /// Upload an attachment
+    /// 上传附件（图片、文件等）
+    ///
+    /// 以 multipart 表单提交到 `/attachments`，返回语雀托管的链接，可以直接嵌入
+    /// Markdown/Lake 文档。文件超过服务端的大小限制时返回 `YuqueError::PayloadTooLarge`。
+    /// multipart 请求体不能被复制，所以不会按重试策略重发。
+    ///
+    /// # Arguments
+    /// * `bytes: Vec<u8>` - 文件内容
+    /// * `filename: &str` - 文件名
+    /// * `content_type: &str` - 文件的 MIME 类型，如 `image/png`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let attachments = yuque.attachments();
+    ///
+    ///     let bytes = std::fs::read("logo.png")?;
+    ///     let url = attachments.upload(bytes, "logo.png", "image/png").await?;
+    ///
+    ///     println!("![logo]({url})");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn upload(
+        &self,
+        bytes: Vec<u8>,
+        filename: &str,
+        content_type: &str,
+    ) -> Result<String, YuqueError> {
+        let url = "/attachments".to_string();
+
+        let part = Part::bytes(bytes)
+            .file_name(filename.to_string())
+            .mime_str(content_type)
+            .map_err(|e| {
+                YuqueError::invalid_params(
+                    &url,
+                    format!("invalid content type {content_type}: {e}"),
+                )
+            })?;
+
+        let request = self
+            .client
+            .multipart(&url, Form::new().part("file", part))?;
+
+        let response = self.client.execute(request).await?;
+
+        let response: YuqueResponse<UploadedAttachment> = handle_response(response, url).await?;
+
+        Ok(response.data.url)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::{MockBackend, Yuque, YuqueError};
+
+    #[test]
+    fn should_return_the_hosted_url() -> Result<(), Box<dyn Error>> {
+        let mock = MockBackend::new().route(
+            Method::POST,
+            "/attachments",
+            200,
+            json!({ "data": { "url": "https://cdn.nlark.com/yuque/0/2023/png/logo.png" } }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let attachments = client.attachments();
+
+        let url = tokio_test::block_on(attachments.upload(vec![0; 16], "logo.png", "image/png"))?;
+
+        assert_eq!(url, "https://cdn.nlark.com/yuque/0/2023/png/logo.png");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_content_type() -> Result<(), Box<dyn Error>> {
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:1".into())
+            .build()?;
+        let attachments = client.attachments();
+
+        let result = tokio_test::block_on(attachments.upload(Vec::new(), "a", "not a mime"));
+
+        assert!(matches!(result, Err(YuqueError::InvalidParams { .. })));
+
+        Ok(())
+    }
+}
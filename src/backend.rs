@@ -0,0 +1,217 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+use std::{fmt::Debug, future::Future, pin::Pin, sync::Arc};
+
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Method;
+use reqwest::{Request, Response};
+#[cfg(not(target_arch = "wasm32"))]
+use serde::Serialize;
+
+use crate::YuqueError;
+
+/// The future returned by [`HttpBackend::execute`].
+pub type BackendFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Response, YuqueError>> + Send + 'a>>;
+
+/// The transport used by [`crate::Yuque`] to send the requests.
+///
+/// By default the requests are sent through the inner `reqwest::Client`. A backend
+/// set through `YuqueBuilder::backend` replaces it, which makes it possible to
+/// fake the server in tests. The requests it receives already carry the auth headers
+/// and the body, while the status checking and the json parsing still happen in
+/// the client, so a backend only needs to produce a `reqwest::Response`.
+///
+/// It only applies to the async client, `blocking::Yuque` always uses the network.
+///
+/// For most tests [`MockBackend`] is enough, implement the trait for anything fancier.
+/// `MockBackend` isn't available on wasm, where `reqwest::Response` can only come from `fetch`.
+pub trait HttpBackend: Send + Sync {
+    /// Send the request and return the raw response.
+    fn execute(&self, request: Request) -> BackendFuture<'_>;
+}
+
+/// The backend of a `Yuque`, kept behind an `Arc` so that the clones share it.
+#[derive(Clone)]
+pub(crate) struct Backend(pub(crate) Arc<dyn HttpBackend>);
+
+impl Debug for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Backend")
+    }
+}
+
+/// An [`HttpBackend`] that answers with canned responses instead of the network.
+///
+/// A route matches when the method is the same and the url path ends with the
+/// given path, the first matching route wins. Requests without a matching route get
+/// a `404`, which surfaces as `YuqueError::NotFound`. Every request is recorded and
+/// can be inspected through `requests`.
+///
+/// # Example
+///
+/// This is the recommended way to test code built on top of this crate:
+///
+/// ```rust
+/// use reqwest::Method;
+/// use serde_json::json;
+/// use yuque_rust::{MockBackend, Yuque};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mock = MockBackend::new().route(
+///         Method::GET,
+///         "/user",
+///         200,
+///         json!({ "data": {
+///             "id": 1, "space_id": 0, "account_id": 1, "type": "User",
+///             "login": "lzzzt", "name": "lzzzt", "avatar_url": "",
+///             "created_at": "2023-01-01T00:00:00.000Z", "updated_at": "2023-01-01T00:00:00.000Z",
+///         } }),
+///     );
+///
+///     let yuque = Yuque::builder()
+///                         .token("token".into())
+///                         .host("https://example.com/api/v2".into())
+///                         .backend(mock.clone())
+///                         .build()?;
+///
+///     let users = yuque.users();
+///     let user = users.current().await?;
+///
+///     assert_eq!(user.data.login, "lzzzt");
+///     assert_eq!(mock.requests(), vec![(Method::GET, "/api/v2/user".to_string())]);
+///     Ok(())
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    routes: Vec<(Method, String, u16, String)>,
+    requests: Arc<Mutex<Vec<(Method, String)>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MockBackend {
+    /// Create a backend without any route.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer the requests of `method` to `path` with `status` and `body` as json.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method of the request.
+    /// * `path` - The end of the url path, e.g. `/repos/lzzzt/sdk-test/docs`.
+    /// * `status` - The status code of the response.
+    /// * `body` - The json body of the response, usually `{ "data": ... }`.
+    pub fn route(
+        mut self,
+        method: Method,
+        path: impl Into<String>,
+        status: u16,
+        body: impl Serialize,
+    ) -> Self {
+        let body = serde_json::to_string(&body).unwrap_or_default();
+        self.routes.push((method, path.into(), status, body));
+        self
+    }
+
+    /// The method and the path (with the query) of every request received so far.
+    pub fn requests(&self) -> Vec<(Method, String)> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn respond(&self, request: &Request) -> Result<Response, YuqueError> {
+        let url = request.url();
+
+        let target = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push((request.method().clone(), target));
+
+        let (status, body) = self
+            .routes
+            .iter()
+            .find(|(method, path, ..)| method == request.method() && url.path().ends_with(path))
+            .map(|(.., status, body)| (*status, body.clone()))
+            .unwrap_or_else(|| (404, r#"{"message":"no mock route"}"#.into()));
+
+        http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(body)
+            .map(Response::from)
+            .map_err(|e| YuqueError::Internal(e.to_string()))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpBackend for MockBackend {
+    fn execute(&self, request: Request) -> BackendFuture<'_> {
+        let response = self.respond(&request);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::Method;
+    use serde_json::json;
+
+    use super::MockBackend;
+    use crate::{Yuque, YuqueError};
+
+    #[test]
+    fn should_route_requests_through_the_backend() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/user",
+            200,
+            json!({ "data": {
+                "id": 1,
+                "space_id": 0,
+                "account_id": 1,
+                "type": "User",
+                "login": "lzzzt",
+                "name": "lzzzt",
+                "avatar_url": "",
+                "created_at": "2023-01-01T00:00:00.000Z",
+                "updated_at": "2023-01-01T00:00:00.000Z",
+            } }),
+        );
+
+        let yuque = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:1".into())
+            .backend(mock.clone())
+            .build()?;
+
+        let users = yuque.users();
+        let user = tokio_test::block_on(users.current())?;
+        assert_eq!(user.data.login, "lzzzt");
+
+        let repos = yuque.repos();
+        let missing = tokio_test::block_on(repos.get("lzzzt/sdk-test", None));
+        assert!(matches!(missing, Err(YuqueError::NotFound { .. })));
+
+        assert_eq!(
+            mock.requests(),
+            vec![
+                (Method::GET, "/api/v2/user".to_string()),
+                (Method::GET, "/api/v2/repos/lzzzt/sdk-test".to_string()),
+            ]
+        );
+
+        Ok(())
+    }
+}
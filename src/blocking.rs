@@ -0,0 +1,440 @@
+//! 同步（阻塞）版本的客户端，需要开启 `blocking` feature
+//!
+//! 基于 `reqwest::blocking::Client`，方法与异步的 `DocsClient`/`ReposClient` 一一对应，
+//! 只是去掉了 `async`/`.await`。流式遍历、克隆仓库等组合接口只提供异步版本。
+//!
+//! 不要在异步运行时（比如 `#[tokio::main]`）中使用。
+
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    Method,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    check_title, group_repos_path, page_query, status_error, user_repos_path, Doc, DocDetail,
+    DocListItem, DocRef, QueryParams, Repo, RepoDetail, RepoListItem, RepoRef, UpdateDoc,
+    UpdateRepo, YuqueError, YuqueResponse, DEFAULT_TIMEOUT,
+};
+
+/// The blocking client of yuque.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use yuque_rust::{blocking, Yuque};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let yuque = Yuque::builder()
+///                         .token("token".into())
+///                         .host("https://www.yuque.com/api/v2".into())
+///                         .build()?;
+///
+///     let yuque = blocking::Yuque::new(yuque)?;
+///
+///     let docs = yuque.docs();
+///
+///     println!("{:?}", docs.list_with_repo("example")?);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Yuque {
+    client: Client,
+    config: crate::Yuque,
+}
+
+impl Yuque {
+    /// Create a blocking client with the token, host and headers of an async client.
+    ///
+    /// The inner `reqwest::blocking::Client` uses the builder `timeout`, proxy and compression
+    /// of the async client, or [`DEFAULT_TIMEOUT`] when it was built from a supplied
+    /// `reqwest::Client`.
+    pub fn new(config: crate::Yuque) -> Result<Self, YuqueError> {
+        let builder = Client::builder().timeout(config.timeout.unwrap_or(DEFAULT_TIMEOUT));
+
+        #[cfg(feature = "compression")]
+        let builder = builder.gzip(config.compression).brotli(config.compression);
+
+        let client =
+            crate::apply_proxy(builder, config.proxy.as_deref(), config.no_proxy)?.build()?;
+
+        Ok(Self { client, config })
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        api: &str,
+        data: Option<String>,
+    ) -> Result<RequestBuilder, YuqueError> {
+        let url = format!("{}{}", self.config.host, api);
+
+        let mut builder = self
+            .client
+            .request(method.clone(), url)
+            .headers(self.config.generate_headers()?);
+
+        if method == Method::POST || method == Method::PUT {
+            builder = builder.header("Content-Type", "application/json");
+
+            if let Some(data) = data {
+                builder = builder.body(data);
+            }
+        }
+
+        if let Some(timeout) = self.config.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(builder)
+    }
+
+    /// Get the blocking client aimed to handle yuque doc.
+    pub fn docs(&self) -> DocsClient {
+        DocsClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the blocking client aimed to handle yuque repo.
+    pub fn repos(&self) -> ReposClient {
+        ReposClient {
+            client: self.clone(),
+        }
+    }
+}
+
+fn ensure_success(response: Response, url: String) -> Result<Response, YuqueError> {
+    let status = response.status();
+
+    if !status.is_client_error() && !status.is_server_error() {
+        return Ok(response);
+    }
+
+    let headers = response.headers().clone();
+    let body = response.text().unwrap_or_default();
+
+    Err(status_error(status.as_u16(), &headers, url, &body))
+}
+
+fn handle_response<T: DeserializeOwned>(
+    response: Response,
+    url: String,
+) -> Result<YuqueResponse<T>, YuqueError> {
+    let body = ensure_success(response, url.clone())?.bytes()?;
+
+    crate::parse_body(url, &body)
+}
+
+/// 同步版本的 [`crate::DocsClient`]
+#[derive(Debug)]
+pub struct DocsClient {
+    client: Yuque,
+}
+
+impl DocsClient {
+    /// 获取仓库下的文档列表，见 [`crate::DocsClient::list_with_repo`]
+    pub fn list_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        let response = self.client.request(Method::GET, &url, None)?.send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 分页获取仓库下的文档列表，见 [`crate::DocsClient::list_with_repo_paged`]
+    pub fn list_with_repo_paged(
+        &self,
+        namespace: impl Into<RepoRef>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        let query = page_query(&url, offset, limit)?;
+
+        let response = self
+            .client
+            .request(Method::GET, &url, None)?
+            .query(&query)
+            .send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 获取文档详情，见 [`crate::DocsClient::get_with_repo_ns`]
+    pub fn get_with_repo_ns(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        self.get_with_repo_ns_query(namespace, doc, data)
+    }
+
+    /// 带查询参数获取文档详情，见 [`crate::DocsClient::get_with_repo_ns_query`]
+    pub fn get_with_repo_ns_query(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let (namespace, doc): (RepoRef, DocRef) = (namespace.into(), doc.into());
+        let url = namespace.doc_path(&doc);
+
+        let response = self
+            .client
+            .request(Method::GET, &url, None)?
+            .query(&query.into())
+            .send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 通过文档编号获取文档详情，见 [`crate::DocsClient::get_by_id`]
+    pub fn get_by_id(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        self.get_with_repo_ns(namespace, DocRef::Id(id), data)
+    }
+
+    /// 创建文档，见 [`crate::DocsClient::create_with_repo`]
+    pub fn create_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        data: Doc,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        check_title(&url, &data)?;
+
+        let data = serde_json::to_string(&data).ok();
+
+        let response = self.client.request(Method::POST, &url, data)?.send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 删除文档，见 [`crate::DocsClient::delete_with_repo`]
+    pub fn delete_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.doc_path(&DocRef::Id(id));
+
+        let response = self.client.request(Method::DELETE, &url, None)?.send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 更新文档，见 [`crate::DocsClient::update_with_repo`]
+    pub fn update_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        data: impl Into<UpdateDoc>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.doc_path(&DocRef::Id(id));
+
+        let data = serde_json::to_string(&data.into()).ok();
+
+        let response = self.client.request(Method::PUT, &url, data)?.send()?;
+
+        handle_response(response, url)
+    }
+}
+
+/// 同步版本的 [`crate::ReposClient`]
+#[derive(Debug)]
+pub struct ReposClient {
+    client: Yuque,
+}
+
+impl ReposClient {
+    /// 获取用户的仓库列表，见 [`crate::ReposClient::list_repo_of_user`]
+    pub fn list_repo_of_user(
+        &self,
+        user: impl ToString,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list(user_repos_path(user), data)
+    }
+
+    /// 带查询参数获取用户的仓库列表，见 [`crate::ReposClient::list_repo_of_user_query`]
+    pub fn list_repo_of_user_query(
+        &self,
+        user: impl ToString,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list(user_repos_path(user), query)
+    }
+
+    /// 获取团队的仓库列表，见 [`crate::ReposClient::list_repo_of_group`]
+    pub fn list_repo_of_group(
+        &self,
+        group: impl ToString,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list(group_repos_path(group), data)
+    }
+
+    /// 带查询参数获取团队的仓库列表，见 [`crate::ReposClient::list_repo_of_group_query`]
+    pub fn list_repo_of_group_query(
+        &self,
+        group: impl ToString,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list(group_repos_path(group), query)
+    }
+
+    /// 为用户创建仓库，见 [`crate::ReposClient::create_repo_of_user`]
+    pub fn create_repo_of_user(
+        &self,
+        user: impl ToString,
+        data: Repo,
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        self.create(user_repos_path(user), data)
+    }
+
+    /// 为团队创建仓库，见 [`crate::ReposClient::create_repo_of_group`]
+    pub fn create_repo_of_group(
+        &self,
+        group: impl ToString,
+        data: Repo,
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        self.create(group_repos_path(group), data)
+    }
+
+    /// 获取仓库详情，见 [`crate::ReposClient::get`]
+    pub fn get(
+        &self,
+        repo: impl Into<RepoRef>,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
+
+        let response = self
+            .client
+            .request(Method::GET, &url, None)?
+            .query(&QueryParams::from(data))
+            .send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 获取仓库的目录，见 [`crate::ReposClient::get_toc`]
+    #[cfg(feature = "toc")]
+    pub fn get_toc(
+        &self,
+        repo: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<Vec<crate::Toc<'_>>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.toc_path();
+
+        let response = self.client.request(Method::GET, &url, None)?.send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 更新仓库信息，见 [`crate::ReposClient::update`]
+    pub fn update(
+        &self,
+        repo: impl Into<RepoRef>,
+        data: impl Into<UpdateRepo>,
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
+
+        let data = serde_json::to_string(&data.into()).ok();
+
+        let response = self.client.request(Method::PUT, &url, data)?.send()?;
+
+        handle_response(response, url)
+    }
+
+    /// 删除仓库，见 [`crate::ReposClient::delete`]
+    pub fn delete(&self, repo: impl Into<RepoRef>) -> Result<(), YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
+
+        let response = self.client.request(Method::DELETE, &url, None)?.send()?;
+
+        ensure_success(response, url)?;
+
+        Ok(())
+    }
+
+    fn list(
+        &self,
+        url: String,
+        data: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        let response = self
+            .client
+            .request(Method::GET, &url, None)?
+            .query(&data.into())
+            .send()?;
+
+        handle_response(response, url)
+    }
+
+    fn create(&self, url: String, data: Repo) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let data = serde_json::to_string(&data).ok();
+
+        let response = self.client.request(Method::POST, &url, data)?.send()?;
+
+        handle_response(response, url)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::YuqueError;
+
+    use super::Yuque;
+
+    fn client() -> Yuque {
+        Yuque::new(
+            crate::Yuque::builder()
+                .token("token".into())
+                .host("http://127.0.0.1:1".into())
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_validate_before_sending() {
+        let docs = client().docs();
+
+        assert!(matches!(
+            docs.list_with_repo_paged("lzzzt/sdk-test", 0, 0),
+            Err(YuqueError::InvalidParams { .. })
+        ));
+    }
+
+    #[test]
+    fn should_surface_network_errors() {
+        let repos = client().repos();
+
+        assert!(matches!(
+            repos.get("lzzzt/sdk-test", None),
+            Err(YuqueError::Request(e)) if e.is_connect()
+        ));
+    }
+}
@@ -1,7 +1,27 @@
-use derive_builder::Builder;
-use reqwest::{header::HeaderMap, Client, RequestBuilder};
+use std::{
+    ops::Not,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use crate::{DocsClient, ReposClient, RequestMethod, YuqueError, DEFAULT_USER_AGENT};
+use derive_builder::{Builder, UninitializedFieldError};
+use rand::Rng;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    multipart::Form,
+    Client, Method, RequestBuilder, Response, Url,
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+
+use crate::{
+    current_user, handle_response, AttachmentsClient, Backend, DocsClient, GroupsClient,
+    HttpBackend, QueryParams, RateLimit, ReposClient, RequestMethod, SearchClient,
+    StatisticsClient, UserDetail, UsersClient, YuqueError, YuqueResponse, DEFAULT_BASE_PATH,
+    DEFAULT_TIMEOUT, DEFAULT_USER_AGENT,
+};
 
 /// The client of yuque.
 ///
@@ -11,290 +31,1836 @@ use crate::{DocsClient, ReposClient, RequestMethod, YuqueError, DEFAULT_USER_AGE
 /// use yuque_rust::Yuque;
 ///
 /// let yuque = Yuque::builder()
-///                     .token("token")
-///                     .host("example_host")
+///                     .token("token".into())
+///                     .host("https://example.com".into())
 ///                     .build()
 ///                     .unwrap();
 ///
+/// assert_eq!(yuque.host, "https://example.com/api/v2");
 /// ```
+///
+/// # Base path
+///
+/// `host` used to be the full base url of the api, e.g. `https://www.yuque.com/api/v2`.
+/// It can now be the bare domain: [`DEFAULT_BASE_PATH`] is appended when `host` has no
+/// path, and `base_path` changes it for self-hosted servers serving the api elsewhere.
+/// Passing the full url to `host` keeps working, so existing code needs no change.
+/// Only a bare `host` pointing at a server serving the api at its root now needs
+/// `base_path("")`.
+///
+/// Unless a `client` is supplied, the inner `reqwest::Client` is built with a
+/// timeout of [`DEFAULT_TIMEOUT`], which can be changed through `timeout`.
+///
+/// `reqwest::Client` is reference counted, so cloning a `Yuque` (which is what
+/// `docs()`, `repos()` and the other sub-client accessors do) is cheap and every
+/// copy shares the same connection pool. Build one client and derive the
+/// sub-clients from it instead of building a client per call.
 #[derive(Default, Builder, Clone, Debug)]
+#[builder(build_fn(validate = "Self::validate", error = "YuqueError"))]
 pub struct Yuque {
-    #[builder(default = "Client::default()")]
+    #[builder(default = "self.default_client()?")]
     pub(crate) client: Client,
-    pub(crate) token: String,
+    /// How the client authenticates, set through `token` or `auth`.
+    #[builder(setter(custom))]
+    pub(crate) auth: AuthMethod,
+    /// The base url of the api: the validated `host` joined with the `base_path`.
+    #[builder(setter(custom), field(type = "ApiUrl", build = "self.api_url()?"))]
     pub host: String,
     #[builder(default = "DEFAULT_USER_AGENT.into()")]
     pub(crate) user_agent: String,
+    #[builder(default, setter(strip_option))]
+    pub(crate) accept_language: Option<String>,
+    /// Extra headers sent with every request, see `generate_headers`.
+    #[builder(default)]
+    pub(crate) headers: HeaderMap,
+    /// The timeout of the inner client, only used when no `client` is supplied.
+    #[builder(
+        setter(strip_option),
+        default = "self.client.is_none().then_some(DEFAULT_TIMEOUT)"
+    )]
+    pub(crate) timeout: Option<Duration>,
+    /// The proxy of the inner client, only used when no `client` is supplied.
+    /// Kept after `build()` for the blocking client.
+    #[builder(setter(custom), default)]
+    #[cfg_attr(not(feature = "blocking"), allow(dead_code))]
+    pub(crate) proxy: Option<String>,
+    /// Disable all proxies of the inner client, including the system ones.
+    #[builder(setter(custom), default)]
+    #[cfg_attr(not(feature = "blocking"), allow(dead_code))]
+    pub(crate) no_proxy: bool,
+    /// Accept gzip/brotli responses in the inner client, see `YuqueBuilder::compression`.
+    /// Kept after `build()` for the blocking client.
+    #[builder(setter(custom), default)]
+    #[cfg_attr(
+        not(all(feature = "compression", feature = "blocking")),
+        allow(dead_code)
+    )]
+    pub(crate) compression: bool,
+    #[builder(setter(skip))]
+    pub(crate) request_timeout: Option<Duration>,
+    #[builder(setter(custom), default)]
+    pub(crate) semaphore: Option<Arc<Semaphore>>,
+    #[builder(setter(custom), default)]
+    pub(crate) retry: Option<RetryPolicy>,
+    #[builder(setter(skip))]
+    pub(crate) rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    /// Replaces the inner client when sending the requests, see [`HttpBackend`].
+    #[builder(setter(custom), default)]
+    pub(crate) backend: Option<Backend>,
 }
 
-impl Yuque {
-    pub fn builder() -> YuqueBuilder {
-        YuqueBuilder::default()
+/// The builder side of `Yuque::host`: what `host` and `base_path` set, joined by `build()`.
+#[derive(Clone, Default)]
+pub(crate) struct ApiUrl {
+    host: Option<String>,
+    base_path: Option<String>,
+}
+
+/// The way to authenticate against the yuque server.
+///
+/// * `Token` - The personal token, sent as `X-Auth-Token`.
+/// * `Bearer` - The OAuth2 access token, sent as `Authorization: Bearer <token>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthMethod {
+    Token(String),
+    Bearer(String),
+}
+
+impl Default for AuthMethod {
+    fn default() -> Self {
+        Self::Token(String::new())
     }
+}
 
-    /// Generate headers for sending to the yuque server.
+impl AuthMethod {
+    fn token_mut(&mut self) -> &mut String {
+        match self {
+            AuthMethod::Token(token) | AuthMethod::Bearer(token) => token,
+        }
+    }
+}
+
+fn validate_host(host: &str) -> Result<(), YuqueError> {
+    let url = Url::parse(host).map_err(|e| {
+        YuqueError::invalid_params(
+            host,
+            format!("host must be an absolute url like https://www.yuque.com ({e})"),
+        )
+    })?;
+
+    if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+        return Err(YuqueError::invalid_params(
+            host,
+            "host must be an http or https url",
+        ));
+    }
+
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(YuqueError::invalid_params(
+            host,
+            "host must not contain a query or a fragment",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether a failed request is worth retrying.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_request() || e.is_timeout()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// There is no tokio timer in the browser, use the one of the js event loop instead.
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
+
+/// Configure the proxy of an internally built client, shared with the blocking client.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn apply_proxy<B: ProxyBuilder>(
+    builder: B,
+    proxy: Option<&str>,
+    no_proxy: bool,
+) -> Result<B, YuqueError> {
+    if no_proxy {
+        return Ok(builder.without_proxy());
+    }
+
+    match proxy {
+        Some(proxy) => {
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| YuqueError::Builder(format!("invalid proxy `{proxy}`: {e}")))?;
+
+            Ok(builder.with_proxy(proxy))
+        }
+        None => Ok(builder),
+    }
+}
+
+/// The part of `reqwest::ClientBuilder` and `reqwest::blocking::ClientBuilder` used by `apply_proxy`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) trait ProxyBuilder: Sized {
+    fn with_proxy(self, proxy: Proxy) -> Self;
+    fn without_proxy(self) -> Self;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ProxyBuilder for reqwest::ClientBuilder {
+    fn with_proxy(self, proxy: Proxy) -> Self {
+        self.proxy(proxy)
+    }
+
+    fn without_proxy(self) -> Self {
+        self.no_proxy()
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl ProxyBuilder for reqwest::blocking::ClientBuilder {
+    fn with_proxy(self, proxy: Proxy) -> Self {
+        self.proxy(proxy)
+    }
+
+    fn without_proxy(self) -> Self {
+        self.no_proxy()
+    }
+}
+
+/// The policy to retry requests failed with a 5xx status or a transient network error.
+///
+/// The delay before the `n`-th retry is picked at random between half of and the
+/// full `base_delay * 2^n`, so that concurrent clients don't retry in lockstep.
+/// Only idempotent requests (`GET`, `PUT`, `DELETE`) are retried, unless `retry_post`
+/// is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub retry_post: bool,
+}
+
+impl RetryPolicy {
+    /// Create a policy that retries idempotent requests.
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            retry_post: false,
+        }
+    }
+
+    fn allows(&self, method: &Method) -> bool {
+        match *method {
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE => true,
+            Method::POST => self.retry_post,
+            _ => false,
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+
+        rand::thread_rng().gen_range(delay / 2..=delay)
+    }
+}
+
+impl YuqueBuilder {
+    fn validate(&self) -> Result<(), YuqueError> {
+        if let Some(host) = &self.host.host {
+            validate_host(host)?;
+        }
+
+        if self.client.is_some() && self.timeout.is_some() {
+            return Err(YuqueError::Builder(
+                "timeout can't be combined with a custom client, set it on the client instead"
+                    .into(),
+            ));
+        }
+
+        // The semaphore only comes from `max_concurrency`, without a permit every request waits forever.
+        if let Some(Some(semaphore)) = &self.semaphore {
+            if semaphore.available_permits() == 0 {
+                return Err(YuqueError::Builder(
+                    "max_concurrency must be greater than 0".into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `host` joined with the `base_path`.
+    ///
+    /// Without a `base_path`, [`DEFAULT_BASE_PATH`] is appended to a bare domain
+    /// while a `host` that already has a path is kept as is.
+    fn api_url(&self) -> Result<String, YuqueError> {
+        let host = self
+            .host
+            .host
+            .as_deref()
+            .ok_or_else(|| UninitializedFieldError::new("host"))?;
+
+        let has_path = Url::parse(host)
+            .map(|url| url.path().trim_matches('/').is_empty().not())
+            .unwrap_or_default();
+
+        match self.host.base_path.as_deref() {
+            Some(base_path) if host.ends_with(base_path) => Ok(host.to_string()),
+            Some(base_path) => Ok(format!("{host}{base_path}")),
+            None if has_path => Ok(host.to_string()),
+            None => Ok(format!("{host}{DEFAULT_BASE_PATH}")),
+        }
+    }
+
+    /// Set the url of the yuque server, e.g. `https://www.yuque.com`.
     ///
-    /// # Returns
+    /// The path of the api is appended to it, see `base_path`. A `host` that already
+    /// contains the path, like `https://www.yuque.com/api/v2`, keeps working.
     ///
-    /// * `Result<HeaderMap, YuqueError>` - The headers wrapped in a result.
+    /// Trailing slashes are removed, so that the api paths can be appended to it.
+    /// `build()` fails with `YuqueError::InvalidParams` when it isn't an absolute
+    /// `http`/`https` url.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The url of the server, with or without the path of the api.
+    pub fn host(&mut self, host: String) -> &mut Self {
+        self.host.host = Some(host.trim().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Set the path of the api under `host`, [`DEFAULT_BASE_PATH`] by default.
+    ///
+    /// Self-hosted servers may serve the api somewhere else. An empty path uses `host`
+    /// as the base url of the api directly.
+    ///
+    /// When `base_path` isn't set, [`DEFAULT_BASE_PATH`] is only appended to a `host`
+    /// without a path, so the old style of passing the full url to `host` still works.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_path` - The path of the api, e.g. `/yuque/api/v2`.
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust
     /// use yuque_rust::Yuque;
     ///
-    ///
     /// let yuque = Yuque::builder()
-    ///                     .token("token")
-    ///                     .host("example_host")
+    ///                     .token("token".into())
+    ///                     .host("https://yuque.example.com".into())
+    ///                     .base_path("/yuque/api/v2")
     ///                     .build()
     ///                     .unwrap();
-    ///   
-    /// let headers = yuque.generate_headers().unwrap();
-    ///
-    /// assert_eq!(headers.get("X-Auth-Token").unwrap(), "token");
-    /// assert_eq!(headers.get("User-Agent").unwrap(), "@yuque/sdk");
     ///
+    /// assert_eq!(yuque.host, "https://yuque.example.com/yuque/api/v2");
     /// ```
-    pub fn generate_headers(&self) -> Result<HeaderMap, YuqueError> {
-        let mut headers = HeaderMap::new();
-
-        headers.insert("X-Auth-Token", self.token.parse()?);
-        headers.insert("User-Agent", self.user_agent.parse()?);
+    pub fn base_path(&mut self, base_path: impl AsRef<str>) -> &mut Self {
+        let base_path = base_path.as_ref().trim().trim_matches('/');
 
-        Ok(headers)
+        self.host.base_path = Some(if base_path.is_empty() {
+            String::new()
+        } else {
+            format!("/{base_path}")
+        });
+        self
     }
 
-    /// Generate requests for sending to the yuque server.
+    /// Authenticate with a personal token, the shortcut of `auth(AuthMethod::Token(token))`.
     ///
     /// # Arguments
     ///
-    /// * `method` - The request method.
     /// * `token` - The token of the user.
-    /// * `api` - The api of the request.
-    /// * `data` - The data of the request.
+    pub fn token(&mut self, token: String) -> &mut Self {
+        self.auth(AuthMethod::Token(token))
+    }
+
+    /// Choose how the client authenticates.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
-    fn request(
-        &self,
-        method: crate::RequestMethod,
-        api: &str,
-        data: Option<String>,
-    ) -> Result<RequestBuilder, YuqueError> {
-        let url = format!("{}{}", self.host, api);
-
-        let request_builder: RequestBuilder = match method {
-            crate::RequestMethod::Get => self.client.get(url).headers(self.generate_headers()?),
-            crate::RequestMethod::Post => {
-                let builder = self
-                    .client
-                    .post(url)
-                    .headers(self.generate_headers()?)
-                    .header("Content-Type", "application/json");
+    /// * `auth` - The auth method, see [`AuthMethod`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yuque_rust::{AuthMethod, Yuque};
+    ///
+    /// let yuque = Yuque::builder()
+    ///                     .auth(AuthMethod::Bearer("access token".into()))
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .build()
+    ///                     .unwrap();
+    ///
+    /// let headers = yuque.generate_headers().unwrap();
+    ///
+    /// assert_eq!(headers.get("Authorization").unwrap(), "Bearer access token");
+    /// ```
+    pub fn auth(&mut self, auth: AuthMethod) -> &mut Self {
+        self.auth = Some(auth);
+        self
+    }
 
-                if let Some(data) = data {
-                    builder.body(data)
-                } else {
-                    builder
-                }
-            }
-            crate::RequestMethod::Put => {
-                let builder = self
-                    .client
-                    .put(url)
-                    .headers(self.generate_headers()?)
-                    .header("Content-Type", "application/json");
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_client(&self) -> Result<Client, YuqueError> {
+        let builder = Client::builder().timeout(self.timeout.flatten().unwrap_or(DEFAULT_TIMEOUT));
 
-                if let Some(data) = data {
-                    builder.body(data)
-                } else {
-                    builder
-                }
-            }
-            crate::RequestMethod::Delete => {
-                self.client.delete(url).headers(self.generate_headers()?)
-            }
+        #[cfg(feature = "compression")]
+        let builder = {
+            let compression = self.compression.unwrap_or_default();
+            builder.gzip(compression).brotli(compression)
         };
 
-        Ok(request_builder)
+        Ok(apply_proxy(
+            builder,
+            self.proxy.as_ref().and_then(Option::as_deref),
+            self.no_proxy.unwrap_or_default(),
+        )?
+        .build()?)
     }
 
-    /// Generate a GET request for sending to the yuque server.
-    ///
-    /// # Arguments
+    /// The fetch based client of wasm has neither a timeout nor a proxy.
+    #[cfg(target_arch = "wasm32")]
+    fn default_client(&self) -> Result<Client, YuqueError> {
+        Ok(Client::builder().build()?)
+    }
+
+    /// Send every request of the inner client through a proxy.
     ///
-    /// * `api` - The api of the request.
+    /// It is ignored when a `client` is supplied, configure the proxy on that client
+    /// instead. An invalid proxy url makes `build()` fail with `YuqueError::Builder`.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    /// * `proxy` - The url of the proxy, e.g. `http://127.0.0.1:7890`.
     ///
     /// # Example
     ///
     /// ```rust
     /// use yuque_rust::Yuque;
     ///
-    /// let client = Yuque::builder()
-    ///                     .token("token")
-    ///                     .host("example_host")
+    /// let yuque = Yuque::builder()
+    ///                     .token("token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .proxy("http://127.0.0.1:7890")
     ///                     .build()
     ///                     .unwrap();
-    ///
-    /// let response = client.get("example").unwrap().send().await.unwrap();
-    ///
-    /// println!("{:#?}", response);
     /// ```
-    ///
-    pub fn get(&self, api: &str) -> Result<RequestBuilder, YuqueError> {
-        self.request(RequestMethod::Get, api, None)
+    pub fn proxy(&mut self, proxy: impl Into<String>) -> &mut Self {
+        self.proxy = Some(Some(proxy.into()));
+        self
     }
 
-    /// Generate a POST request for sending to the yuque server.
-    ///
-    /// # Arguments
-    ///
-    /// * `api` - The api of the request.
-    /// * `data` - The data of the request.
+    /// Don't use any proxy for the inner client, including the ones from the
+    /// `HTTP_PROXY`/`HTTPS_PROXY` environment variables.
     ///
-    /// # Returns
+    /// Like `proxy`, it is ignored when a `client` is supplied.
+    pub fn no_proxy(&mut self) -> &mut Self {
+        self.no_proxy = Some(true);
+        self
+    }
+
+    /// Ask for gzip/brotli compressed responses, which cuts the transfer time of bulk
+    /// doc fetching over slow links.
     ///
-    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    /// The inner client then sends `Accept-Encoding: gzip, br` and decompresses the
+    /// bodies transparently. Requires the `compression` feature, which pulls in the
+    /// `gzip` and `brotli` features of `reqwest`. Like `proxy`, it is ignored when a
+    /// `client` is supplied, enable `gzip`/`brotli` on that client instead.
     ///
     /// # Example
     ///
     /// ```rust
     /// use yuque_rust::Yuque;
     ///
-    /// let client = Yuque::builder()
-    ///                     .token("token")
-    ///                     .host("example_host")
+    /// let yuque = Yuque::builder()
+    ///                     .token("token".into())
+    ///                     .host("https://example.com".into())
+    ///                     .compression()
     ///                     .build()
     ///                     .unwrap();
-    ///
-    /// let response = client.post("example", Some("data".into())).unwrap().send().await.unwrap();
-    ///
-    /// println!("{:#?}", response);
-    ///
     /// ```
-    pub fn post(&self, api: &str, data: Option<String>) -> Result<RequestBuilder, YuqueError> {
-        self.request(RequestMethod::Post, api, data)
+    #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+    pub fn compression(&mut self) -> &mut Self {
+        self.compression = Some(true);
+        self
     }
 
-    /// Generate a PUT request for sending to the yuque server.
+    /// Send the requests through a custom [`HttpBackend`] instead of the network.
+    ///
+    /// This is the seam for faking the server in tests, see [`MockBackend`].
+    /// The `client`, `timeout` and `proxy` settings don't apply to the requests
+    /// sent through it.
     ///
     /// # Arguments
     ///
-    /// * `api` - The api of the request.
-    /// * `data` - The data of the request.
+    /// * `backend` - The transport that answers the requests.
+    pub fn backend(&mut self, backend: impl HttpBackend + 'static) -> &mut Self {
+        self.backend = Some(Some(Backend(Arc::new(backend))));
+        self
+    }
+
+    /// Limit the number of in-flight requests of the client.
     ///
-    /// # Returns
+    /// The limit is shared by the built client and everything derived from it
+    /// (its clones, `with_timeout` copies and the `docs()`/`repos()` sub-clients),
+    /// so it caps the requests of that client instance across all tasks. It only
+    /// applies to the requests sent by the typed methods, not to the raw
+    /// `RequestBuilder`s returned by `get`/`post`/`put`/`delete`.
     ///
-    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    /// # Arguments
+    ///
+    /// * `max_concurrency` - The maximum number of concurrent requests, `build()` fails
+    ///   with `YuqueError::Builder` when it is `0`.
     ///
     /// # Example
     ///
     /// ```rust
     /// use yuque_rust::Yuque;
     ///
-    /// let client = Yuque::builder()
-    ///                     .token("token")
-    ///                     .host("example_host")
+    /// let yuque = Yuque::builder()
+    ///                     .token("token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .max_concurrency(4)
     ///                     .build()
     ///                     .unwrap();
-    ///
-    /// let response = client.put("example", Some("data".into())).unwrap().send().await.unwrap();
-    ///
-    /// println!("{:#?}", response);
-    ///
     /// ```
-    pub fn put(&self, api: &str, data: Option<String>) -> Result<RequestBuilder, YuqueError> {
-        self.request(RequestMethod::Put, api, data)
+    pub fn max_concurrency(&mut self, max_concurrency: usize) -> &mut Self {
+        self.semaphore = Some(Some(Arc::new(Semaphore::new(max_concurrency))));
+        self
     }
 
-    /// Generate a DELETE request for sending to the yuque server.
+    /// Retry idempotent requests failed with a 5xx status or a transient network error.
     ///
-    /// # Arguments
-    ///
-    /// * `api` - The api of the request.
+    /// See [`RetryPolicy`] for the backoff, use `retry_policy` to also retry `POST`.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    /// * `max_retries` - The maximum number of retries of each request.
+    /// * `base_delay` - The delay before the first retry, doubled for each next one.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use yuque_rust::Yuque;
-    /// let client = Yuque::builder()
-    ///                    .token("token")
-    ///                    .host("example_host")
-    ///                    .build()
-    ///                    .unwrap();
+    /// use std::time::Duration;
     ///
-    /// let response = client.delete("example").unwrap().send().await.unwrap();
+    /// use yuque_rust::Yuque;
     ///
-    /// println!("{:#?}", response);
+    /// let yuque = Yuque::builder()
+    ///                     .token("token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .retry(3, Duration::from_millis(200))
+    ///                     .build()
+    ///                     .unwrap();
     /// ```
-    pub fn delete(&self, api: &str) -> Result<RequestBuilder, YuqueError> {
-        self.request(RequestMethod::Delete, api, None)
+    pub fn retry(&mut self, max_retries: u32, base_delay: Duration) -> &mut Self {
+        self.retry_policy(RetryPolicy::new(max_retries, base_delay))
     }
 
-    /// Get the client aimed to handle yuque doc.
+    /// Retry requests with the given policy.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `DocsClient` - The client aimed to handle yuque doc.
+    /// * `policy` - The retry policy.
+    pub fn retry_policy(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.retry = Some(Some(policy));
+        self
+    }
+}
+
+impl Yuque {
+    pub fn builder() -> YuqueBuilder {
+        YuqueBuilder::default()
+    }
+
+    /// Get a copy of the client whose requests use the given timeout.
+    ///
+    /// The timeout is applied to every request sent through the returned client
+    /// (and the sub-clients created from it), and takes precedence over the timeout
+    /// of the underlying `reqwest::Client`. The original client is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The timeout of each request.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
     /// use yuque_rust::Yuque;
     ///
-    /// let client = Yuque::builder()
-    ///                   .token("token")
-    ///                   .host("example_host")
-    ///                   .build()
-    ///                   .unwrap();
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
     ///
-    /// let docs_client = client.docs();
+    ///     let docs_client = client.with_timeout(Duration::from_secs(120)).docs();
     ///
-    /// let response = docs_client.get("example").unwrap().send().await.unwrap();
+    ///     let response = docs_client.list_with_repo("example").await?;
     ///
-    /// println!("{:#?}", response);
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn docs(&self) -> DocsClient {
-        DocsClient {
-            client: self.clone(),
+    pub fn with_timeout(&self, timeout: Duration) -> Yuque {
+        Yuque {
+            request_timeout: Some(timeout),
+            ..self.clone()
         }
     }
-    /// Get the client aimed to handle yuque repo.
+
+    /// The timeout the inner client was built with.
     ///
-    /// # Returns
+    /// It is `None` when the client was built from a supplied `reqwest::Client`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The rate limit reported by the last response that carried rate-limit headers.
     ///
-    /// * `ReposClient` - The client aimed to handle yuque repo.
+    /// It is shared by the client, its clones and its sub-clients, so it reflects the
+    /// latest response received by any of them.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
-    /// let client = Yuque::builder()
-    ///                     .token("token")
-    ///                     .host("example_host")
-    ///                     .build()
-    ///                     .unwrap();
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
     ///
-    /// let repos_client = client.repos();
+    ///     let docs_client = client.docs();
     ///
-    /// let response = repos_client.get("example").unwrap().send().await.unwrap();
+    ///     docs_client.list_with_repo("example").await?;
     ///
-    /// println!("{:#?}", response);
+    ///     if let Some(rate_limit) = client.last_rate_limit() {
+    ///         println!("{:?} requests left", rate_limit.remaining);
+    ///     }
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn repos(&self) -> ReposClient {
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self
+            .rate_limit
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Replace the token of the client, e.g. after it has expired.
+    ///
+    /// The auth method is kept, so a bearer token is replaced by another bearer token.
+    ///
+    /// The underlying `reqwest::Client` (and its connection pool) is kept. Sub-clients
+    /// and copies created before the call still hold the old token, so create them
+    /// again from this client afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The new token, must not be empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yuque_rust::Yuque;
+    ///
+    /// let mut client = Yuque::builder()
+    ///                     .token("expired token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .build()
+    ///                     .unwrap();
+    ///
+    /// client.set_token("new token".into()).unwrap();
+    /// ```
+    pub fn set_token(&mut self, token: String) -> Result<(), YuqueError> {
+        if token.trim().is_empty() {
+            return Err(YuqueError::invalid_params(
+                self.host.clone(),
+                "token must not be empty",
+            ));
+        }
+
+        *self.auth.token_mut() = token;
+
+        Ok(())
+    }
+
+    /// Generate headers for sending to the yuque server.
+    ///
+    /// `Accept-Language` is only sent when `accept_language` is set on the builder.
+    /// The `headers` set on the builder are merged in afterwards and replace the
+    /// defaults with the same name, except `X-Auth-Token` and `Authorization`, which
+    /// are decided by the auth method and can't be overridden.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HeaderMap, YuqueError>` - The headers wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yuque_rust::Yuque;
+    ///
+    ///
+    /// let yuque = Yuque::builder()
+    ///                     .token("token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .build()
+    ///                     .unwrap();
+    ///   
+    /// let headers = yuque.generate_headers().unwrap();
+    ///
+    /// assert_eq!(headers.get("X-Auth-Token").unwrap(), "token");
+    /// assert_eq!(headers.get("User-Agent").unwrap(), "@yuque/sdk");
+    ///
+    /// ```
+    pub fn generate_headers(&self) -> Result<HeaderMap, YuqueError> {
+        let mut headers = HeaderMap::new();
+
+        match &self.auth {
+            AuthMethod::Token(token) => headers.insert("X-Auth-Token", token.parse()?),
+            AuthMethod::Bearer(token) => {
+                headers.insert(AUTHORIZATION, format!("Bearer {token}").parse()?)
+            }
+        };
+        headers.insert("User-Agent", self.user_agent.parse()?);
+
+        if let Some(accept_language) = &self.accept_language {
+            headers.insert("Accept-Language", accept_language.parse()?);
+        }
+
+        let custom = || {
+            self.headers
+                .iter()
+                .filter(|(name, _)| *name != "X-Auth-Token" && *name != AUTHORIZATION)
+        };
+
+        for (name, _) in custom() {
+            headers.remove(name);
+        }
+
+        for (name, value) in custom() {
+            headers.append(name, value.clone());
+        }
+
+        Ok(headers)
+    }
+
+    /// Generate requests for sending to the yuque server.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The request method.
+    /// * `token` - The token of the user.
+    /// * `api` - The api of the request.
+    /// * `data` - The data of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    fn request(
+        &self,
+        method: crate::RequestMethod,
+        api: &str,
+        data: Option<String>,
+    ) -> Result<RequestBuilder, YuqueError> {
+        let url = format!("{}{}", self.host, api);
+
+        let request_builder: RequestBuilder = match method {
+            crate::RequestMethod::Get => self.client.get(url).headers(self.generate_headers()?),
+            crate::RequestMethod::Post => {
+                let builder = self
+                    .client
+                    .post(url)
+                    .headers(self.generate_headers()?)
+                    .header("Content-Type", "application/json");
+
+                if let Some(data) = data {
+                    builder.body(data)
+                } else {
+                    builder
+                }
+            }
+            crate::RequestMethod::Put => {
+                let builder = self
+                    .client
+                    .put(url)
+                    .headers(self.generate_headers()?)
+                    .header("Content-Type", "application/json");
+
+                if let Some(data) = data {
+                    builder.body(data)
+                } else {
+                    builder
+                }
+            }
+            crate::RequestMethod::Delete => {
+                self.client.delete(url).headers(self.generate_headers()?)
+            }
+        };
+
+        Ok(self.apply_request_timeout(request_builder))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply_request_timeout(&self, request_builder: RequestBuilder) -> RequestBuilder {
+        match self.request_timeout {
+            Some(timeout) => request_builder.timeout(timeout),
+            None => request_builder,
+        }
+    }
+
+    /// Requests can't time out on wasm, the timeout is ignored there.
+    #[cfg(target_arch = "wasm32")]
+    fn apply_request_timeout(&self, request_builder: RequestBuilder) -> RequestBuilder {
+        request_builder
+    }
+
+    /// Generate a multipart POST request, e.g. for uploading attachments.
+    ///
+    /// Unlike `post`, the `Content-Type` (with the boundary) is set by the form.
+    pub(crate) fn multipart(&self, api: &str, form: Form) -> Result<RequestBuilder, YuqueError> {
+        let request_builder = self
+            .client
+            .post(format!("{}{}", self.host, api))
+            .headers(self.generate_headers()?)
+            .multipart(form);
+
+        Ok(self.apply_request_timeout(request_builder))
+    }
+
+    /// Generate a GET request for sending to the yuque server.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The api of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let response = client.get("example")?.send().await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn get(&self, api: &str) -> Result<RequestBuilder, YuqueError> {
+        self.request(RequestMethod::Get, api, None)
+    }
+
+    /// Generate a POST request for sending to the yuque server.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The api of the request.
+    /// * `data` - The data of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let response = client.post("example", Some("data".into()))?.send().await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn post(&self, api: &str, data: Option<String>) -> Result<RequestBuilder, YuqueError> {
+        self.request(RequestMethod::Post, api, data)
+    }
+
+    /// Generate a PUT request for sending to the yuque server.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The api of the request.
+    /// * `data` - The data of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let response = client.put("example", Some("data".into()))?.send().await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn put(&self, api: &str, data: Option<String>) -> Result<RequestBuilder, YuqueError> {
+        self.request(RequestMethod::Put, api, data)
+    }
+
+    /// Generate a DELETE request for sending to the yuque server.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The api of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RequestBuilder, YuqueError>` - The request builder wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let response = client.delete("example")?.send().await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn delete(&self, api: &str) -> Result<RequestBuilder, YuqueError> {
+        self.request(RequestMethod::Delete, api, None)
+    }
+
+    /// Send a request built by this client.
+    ///
+    /// When `max_concurrency` is set, a permit is held until the response headers
+    /// have been received. When a retry policy is set, the request is sent again
+    /// according to [`RetryPolicy`]; requests whose body can't be cloned are sent once.
+    ///
+    /// With the `tracing` feature, every call is wrapped in a `yuque_request` span
+    /// recording the method, the path and the status code. Headers, and so the
+    /// token, are never recorded.
+    pub(crate) async fn execute(&self, request: RequestBuilder) -> Result<Response, YuqueError> {
+        let built = request.try_clone().and_then(|request| request.build().ok());
+
+        let method = built.as_ref().map(|request| request.method().clone());
+
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+
+            let span = tracing::info_span!(
+                "yuque_request",
+                method = %method.as_ref().map(Method::as_str).unwrap_or_default(),
+                path = %built.as_ref().map(|request| request.url().path()).unwrap_or_default(),
+                status = tracing::field::Empty,
+            );
+
+            self.execute_with_retry(request, method)
+                .instrument(span)
+                .await
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        self.execute_with_retry(request, method).await
+    }
+
+    async fn execute_with_retry(
+        &self,
+        request: RequestBuilder,
+        method: Option<Method>,
+    ) -> Result<Response, YuqueError> {
+        let Some(policy) = self
+            .retry
+            .filter(|policy| method.as_ref().is_some_and(|method| policy.allows(method)))
+        else {
+            return self.send(request).await;
+        };
+
+        let mut attempt = 0;
+
+        loop {
+            let Some(current) = request.try_clone() else {
+                return self.send(request).await;
+            };
+
+            let retryable = attempt < policy.max_retries;
+
+            match self.send(current).await {
+                Ok(response) if retryable && response.status().is_server_error() => {}
+                Err(YuqueError::Request(e)) if retryable && is_transient(&e) => {}
+                result => return result,
+            }
+
+            sleep(policy.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response, YuqueError> {
+        let _permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(|e| YuqueError::Internal(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let response = match &self.backend {
+            Some(Backend(backend)) => backend.execute(request.build()?).await,
+            None => request.send().await.map_err(YuqueError::from),
+        };
+
+        #[cfg(feature = "tracing")]
+        if let Err(e) = &response {
+            tracing::warn!(error = %e, "failed to send the request");
+        }
+
+        let response = response?;
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("status", response.status().as_u16());
+
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            *self
+                .rate_limit
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(rate_limit);
+        }
+
+        Ok(response)
+    }
+
+    /// Send a request to any api and deserialize the `data` of the response.
+    ///
+    /// This is a lower-level escape hatch for the endpoints that don't have a typed
+    /// wrapper yet. It takes care of the auth headers, the status code checking and
+    /// the json parsing, while the caller is responsible for the api path, the body
+    /// and the shape of `T`. The body is ignored for `GET` and `DELETE` requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The request method.
+    /// * `api` - The api of the request, e.g. `/user`.
+    /// * `data` - The json body of the request.
+    /// * `query` - The query params of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<YuqueResponse<T>, YuqueError>` - The deserialized response wrapped in a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::{RequestMethod, UserDetail, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let response = client
+    ///         .call::<UserDetail>(RequestMethod::Get, "/user", None, None)
+    ///         .await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: RequestMethod,
+        api: &str,
+        data: Option<String>,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<T>, YuqueError> {
+        let request = self.request(method, api, data)?.query(&query.into());
+
+        let response = self.execute(request).await?;
+
+        handle_response(response, api.into()).await
+    }
+
+    /// Get the client aimed to handle yuque doc.
+    ///
+    /// # Returns
+    ///
+    /// * `DocsClient` - The client aimed to handle yuque doc.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let docs_client = client.docs();
+    ///
+    ///     let response = docs_client.list_with_repo("example").await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn docs(&self) -> DocsClient {
+        DocsClient {
+            client: self.clone(),
+        }
+    }
+    /// Get the client aimed to handle yuque repo.
+    ///
+    /// # Returns
+    ///
+    /// * `ReposClient` - The client aimed to handle yuque repo.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let repos_client = client.repos();
+    ///
+    ///     let response = repos_client.get("example", None).await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn repos(&self) -> ReposClient {
         ReposClient {
             client: self.clone(),
         }
     }
+    /// Get the client aimed to handle yuque group.
+    ///
+    /// # Returns
+    ///
+    /// * `GroupsClient` - The client aimed to handle yuque group.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::{GroupProfile, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let groups_client = client.groups();
+    ///
+    ///     let response = groups_client.update_profile("example", GroupProfile::default()).await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn groups(&self) -> GroupsClient {
+        GroupsClient {
+            client: self.clone(),
+        }
+    }
+    /// Get the client aimed to handle yuque user.
+    ///
+    /// # Returns
+    ///
+    /// * `UsersClient` - The client aimed to handle yuque user.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let users_client = client.users();
+    ///
+    ///     let response = users_client.current().await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn users(&self) -> UsersClient {
+        UsersClient {
+            client: self.clone(),
+        }
+    }
+    /// Check that the server is reachable and the token is valid.
+    ///
+    /// It calls `/user` like `UsersClient::current` and returns the authenticated
+    /// user, but a `401` is reported as `YuqueError::InvalidToken` instead of the
+    /// generic `YuqueError::InvalidUserInfo`, so that tools can fail fast at startup
+    /// with a clear message.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                     .token("your token".into())
+    ///                     .host("https://example.com/api/v2".into())
+    ///                     .build()?;
+    ///
+    ///     let user = client.verify().await?;
+    ///
+    ///     println!("logged in as {}", user.login);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify(&self) -> Result<UserDetail<'_>, YuqueError> {
+        match current_user(self).await {
+            Ok(response) => Ok(response.data),
+            Err(YuqueError::InvalidUserInfo { url, .. }) => Err(YuqueError::InvalidToken { url }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the client aimed to upload attachments.
+    ///
+    /// # Returns
+    ///
+    /// * `AttachmentsClient` - The client aimed to upload attachments.
+    pub fn attachments(&self) -> AttachmentsClient {
+        AttachmentsClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the client aimed to search yuque docs and repos.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchClient` - The client aimed to search yuque docs and repos.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let search_client = client.search();
+    ///
+    ///     let response = search_client.docs("example", &[]).await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search(&self) -> SearchClient {
+        SearchClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the client aimed to read the statistics of repos and groups.
+    ///
+    /// # Returns
+    ///
+    /// * `StatisticsClient` - The client aimed to read the statistics of repos and groups.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Yuque::builder()
+    ///                         .token("token".into())
+    ///                         .host("https://example.com/api/v2".into())
+    ///                         .build()?;
+    ///
+    ///     let statistics_client = client.statistics();
+    ///
+    ///     let response = statistics_client.repo("example").await?;
+    ///
+    ///     println!("{:#?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn statistics(&self) -> StatisticsClient {
+        StatisticsClient {
+            client: self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{error::Error, sync::Arc, time::Duration};
+
+    use reqwest::{header::HeaderMap, Client, Method};
+
+    use crate::{
+        AuthMethod, MockBackend, RequestMethod, RetryPolicy, Yuque, YuqueError, DEFAULT_TIMEOUT,
+    };
+
+    fn client() -> Yuque {
+        Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_verify_token() -> Result<(), Box<dyn Error>> {
+        let user = serde_json::json!({ "data": {
+            "id": 1,
+            "space_id": 0,
+            "account_id": 1,
+            "type": "User",
+            "login": "lzzzt",
+            "name": "lzzzt",
+            "avatar_url": "",
+            "created_at": "2023-01-01T00:00:00.000Z",
+            "updated_at": "2023-01-01T00:00:00.000Z",
+        } });
+
+        let valid = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .backend(MockBackend::new().route(Method::GET, "/user", 200, user))
+            .build()?;
+
+        assert_eq!(tokio_test::block_on(valid.verify())?.login, "lzzzt");
+
+        let invalid = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .backend(MockBackend::new().route(
+                Method::GET,
+                "/user",
+                401,
+                serde_json::json!({ "message": "Unauthorized" }),
+            ))
+            .build()?;
+
+        let err = tokio_test::block_on(invalid.verify()).unwrap_err();
+        assert!(matches!(err, YuqueError::InvalidToken { .. }));
+        assert!(err.to_string().contains("token is invalid"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn should_negotiate_compression() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        runtime.block_on(async {
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/user"))
+                .and(header_regex("accept-encoding", "gzip"))
+                .and(header_regex("accept-encoding", "br"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": {
+                        "id": 1,
+                        "space_id": 0,
+                        "account_id": 1,
+                        "type": "User",
+                        "login": "lzzzt",
+                        "name": "lzzzt",
+                        "avatar_url": "",
+                        "created_at": "2023-01-01T00:00:00.000Z",
+                        "updated_at": "2023-01-01T00:00:00.000Z",
+                    }
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = Yuque::builder()
+                .token("token".into())
+                .host(server.uri())
+                .compression()
+                .build()?;
+
+            assert_eq!(client.verify().await?.login, "lzzzt");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn should_apply_method_level_timeout() -> Result<(), Box<dyn Error>> {
+        let client = client();
+
+        let request = client.get("/user")?.build()?;
+        assert_eq!(request.timeout(), None);
+
+        let request = client
+            .with_timeout(Duration::from_secs(120))
+            .get("/user")?
+            .build()?;
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(120)));
+
+        let request = client
+            .with_timeout(Duration::from_secs(1))
+            .docs()
+            .client
+            .delete("/repos/1/docs/1")?
+            .build()?;
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_default_timeout_unless_client_is_supplied() -> Result<(), Box<dyn Error>> {
+        assert_eq!(client().timeout(), Some(DEFAULT_TIMEOUT));
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        assert_eq!(client.timeout(), Some(Duration::from_secs(5)));
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .client(Client::new())
+            .build()?;
+
+        assert_eq!(client.timeout(), None);
+
+        assert!(Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .client(Client::new())
+            .timeout(Duration::from_secs(5))
+            .build()
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_validate_and_normalize_host() -> Result<(), Box<dyn Error>> {
+        let build = |host: &str| {
+            Yuque::builder()
+                .token("token".into())
+                .host(host.into())
+                .build()
+        };
+
+        assert!(matches!(
+            build("example.com"),
+            Err(YuqueError::InvalidParams { url, .. }) if url == "example.com"
+        ));
+        assert!(matches!(
+            build("ftp://x.com"),
+            Err(YuqueError::InvalidParams { .. })
+        ));
+
+        let client = build("https://x.com/")?;
+        assert_eq!(client.host, "https://x.com/api/v2");
+
+        let client = build("https://x.com/api/v2//")?;
+        assert_eq!(
+            client.get("/user")?.build()?.url().as_str(),
+            "https://x.com/api/v2/user"
+        );
+
+        let with_base_path = |host: &str, base_path: &str| {
+            Yuque::builder()
+                .token("token".into())
+                .host(host.into())
+                .base_path(base_path)
+                .build()
+        };
+
+        assert_eq!(
+            with_base_path("https://x.com", "yuque/api/v2/")?.host,
+            "https://x.com/yuque/api/v2"
+        );
+        assert_eq!(
+            with_base_path("https://x.com/yuque/api/v2", "/yuque/api/v2")?.host,
+            "https://x.com/yuque/api/v2"
+        );
+        assert_eq!(with_base_path("https://x.com", "/")?.host, "https://x.com");
+
+        assert!(matches!(
+            Yuque::builder().token("token".into()).build(),
+            Err(YuqueError::Builder(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_proxy_at_build() -> Result<(), Box<dyn Error>> {
+        let builder = || {
+            let mut builder = Yuque::builder();
+            builder
+                .token("token".into())
+                .host("https://www.yuque.com/api/v2".into());
+            builder
+        };
+
+        assert!(builder().proxy("http://127.0.0.1:7890").build().is_ok());
+        assert!(builder().no_proxy().build().is_ok());
+        assert!(matches!(
+            builder().proxy("http://exa mple:7890").build(),
+            Err(YuqueError::Builder(message)) if message.contains("exa mple")
+        ));
+
+        // 传入自定义 client 时忽略代理
+        assert!(builder()
+            .client(Client::new())
+            .proxy("http://exa mple:7890")
+            .build()
+            .is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_retry_only_allowed_methods_with_jittered_backoff() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+
+        assert!(policy.allows(&Method::GET));
+        assert!(policy.allows(&Method::DELETE));
+        assert!(!policy.allows(&Method::POST));
+        assert!(RetryPolicy {
+            retry_post: true,
+            ..policy
+        }
+        .allows(&Method::POST));
+
+        for attempt in 0..3 {
+            let delay = policy.delay(attempt);
+            let max = Duration::from_millis(100 * 2u64.pow(attempt));
+
+            assert!(delay >= max / 2 && delay <= max);
+        }
+    }
+
+    #[test]
+    fn should_retry_network_errors_up_to_max_retries() {
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:1".into())
+            .retry(2, Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let result = tokio_test::block_on(client.call::<serde_json::Value>(
+            RequestMethod::Get,
+            "/user",
+            None,
+            None,
+        ));
+
+        assert!(matches!(result, Err(YuqueError::Request(e)) if e.is_connect()));
+    }
+
+    #[test]
+    fn should_resend_server_errors_up_to_max_retries() {
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/user",
+            500,
+            serde_json::json!({ "message": "Internal Server Error" }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .retry(2, Duration::from_millis(1))
+            .backend(mock.clone())
+            .build()
+            .unwrap();
+
+        let result = tokio_test::block_on(client.call::<serde_json::Value>(
+            RequestMethod::Get,
+            "/user",
+            None,
+            None,
+        ));
+
+        assert!(result.is_err());
+        assert_eq!(mock.requests().len(), 2 + 1);
+        assert!(mock
+            .requests()
+            .iter()
+            .all(|(method, path)| method == Method::GET && path == "/api/v2/user"));
+    }
+
+    #[test]
+    fn should_not_resend_client_errors() {
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/user",
+            403,
+            serde_json::json!({ "message": "Forbidden" }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .retry(2, Duration::from_millis(1))
+            .backend(mock.clone())
+            .build()
+            .unwrap();
+
+        let result = tokio_test::block_on(client.call::<serde_json::Value>(
+            RequestMethod::Get,
+            "/user",
+            None,
+            None,
+        ));
+
+        assert!(result.is_err());
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[test]
+    fn should_use_new_token_after_set_token() -> Result<(), Box<dyn Error>> {
+        let mut client = client();
+
+        assert!(client.set_token("  ".into()).is_err());
+        assert_eq!(
+            client.generate_headers()?.get("X-Auth-Token").unwrap(),
+            "token"
+        );
+
+        client.set_token("new token".into())?;
+
+        assert_eq!(
+            client.generate_headers()?.get("X-Auth-Token").unwrap(),
+            "new token"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_merge_custom_headers_but_keep_token() -> Result<(), Box<dyn Error>> {
+        let mut custom = HeaderMap::new();
+        custom.insert("X-Request-Id", "42".parse()?);
+        custom.insert("User-Agent", "my-app".parse()?);
+        custom.insert("X-Auth-Token", "hijacked".parse()?);
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .headers(custom)
+            .build()?;
+
+        let headers = client.generate_headers()?;
+
+        assert_eq!(headers.get("X-Request-Id").unwrap(), "42");
+        assert_eq!(headers.get("User-Agent").unwrap(), "my-app");
+        assert_eq!(headers.get("X-Auth-Token").unwrap(), "token");
+        assert_eq!(self::client().generate_headers()?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_send_bearer_token_with_oauth() -> Result<(), Box<dyn Error>> {
+        let mut custom = HeaderMap::new();
+        custom.insert("Authorization", "Basic hijacked".parse()?);
+
+        let mut client = Yuque::builder()
+            .auth(AuthMethod::Bearer("access".into()))
+            .host("https://www.yuque.com/api/v2".into())
+            .headers(custom)
+            .build()?;
+
+        let headers = client.generate_headers()?;
+
+        assert_eq!(headers.get("Authorization").unwrap(), "Bearer access");
+        assert!(headers.get("X-Auth-Token").is_none());
+
+        client.set_token("refreshed".into())?;
+
+        assert_eq!(client.auth, AuthMethod::Bearer("refreshed".into()));
+        assert_eq!(
+            client.generate_headers()?.get("Authorization").unwrap(),
+            "Bearer refreshed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_send_accept_language_when_set() -> Result<(), Box<dyn Error>> {
+        assert!(client()
+            .generate_headers()?
+            .get("Accept-Language")
+            .is_none());
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .accept_language("en-US".into())
+            .build()?;
+
+        assert_eq!(
+            client.generate_headers()?.get("Accept-Language").unwrap(),
+            "en-US"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_share_the_transport_across_sub_clients() -> Result<(), Box<dyn Error>> {
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/repos/lzzzt/sdk-test/docs",
+                200,
+                serde_json::json!({ "data": [] }),
+            )
+            .route(
+                Method::GET,
+                "/users/lzzzt/repos",
+                200,
+                serde_json::json!({ "data": [] }),
+            );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?;
+
+        tokio_test::block_on(async {
+            for _ in 0..3 {
+                client.docs().list_with_repo("lzzzt/sdk-test").await?;
+                client.repos().list_repo_of_user("lzzzt", None).await?;
+            }
+
+            Ok::<_, YuqueError>(())
+        })?;
+
+        assert_eq!(mock.requests().len(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_share_concurrency_limit_between_copies() -> Result<(), Box<dyn Error>> {
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .max_concurrency(2)
+            .build()?;
+
+        let semaphore = client.semaphore.clone().unwrap();
+        let docs = client.with_timeout(Duration::from_secs(1)).docs();
+
+        assert_eq!(semaphore.available_permits(), 2);
+        assert!(Arc::ptr_eq(
+            &semaphore,
+            docs.client.semaphore.as_ref().unwrap()
+        ));
+        assert!(self::client().semaphore.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_zero_max_concurrency() {
+        let result = Yuque::builder()
+            .token("token".into())
+            .host("https://www.yuque.com/api/v2".into())
+            .max_concurrency(0)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(YuqueError::Builder(message)) if message.contains("max_concurrency")
+        ));
+    }
 }
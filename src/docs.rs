@@ -1,12 +1,32 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    io,
+    path::{Component, Path},
+};
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, Utc};
 use derive_builder::Builder;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::TryStreamExt;
+use futures::{stream, Stream, StreamExt};
+use reqwest::{
+    header::{HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH},
+    Response, Url,
+};
 use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::{fs, io::AsyncWriteExt};
 
 use crate::{
-    gen_random_slug, judge_status_code, number_to_bool, option_time_serde, time_serde,
-    RepoListItem, User, Yuque, YuqueError, YuqueFormat, YuqueResponse,
+    ensure_success, gen_random_slug, handle_response, number_to_bool, option_time_serde,
+    time_serde, DocRef, QueryParams, RepoListItem, RepoRef, SlugGenerator, User, Visibility, Yuque,
+    YuqueError, YuqueFormat, YuqueResponse, DOC_SLUG_LEN,
 };
 
 /// 文档列表项
@@ -19,16 +39,17 @@ use crate::{
 /// * `user_id: i32` - 文档创建人 user_id
 /// * `format: YuqueFormat` - 描述了正文的格式 [asl, markdown]
 /// * `public: bool` - 是否公开 [1 - 公开, 0 - 私密]
-/// * `status: bool` - 状态 [1 - 正常, 0 - 草稿]
+/// * `status: DocStatus` - 状态 [1 - 正常, 0 - 草稿]
 /// * `likes_count: u16` - 喜欢数量
 /// * `comments_count: u16` - 评论数量
-/// * `content_updated_at: Option<DateTime<Local>>` - 文档内容更新时间
-/// * `book: Repo<'a>` - <Repo> 所属知识库
-/// * `user: User<'a>` - <User> 所属团队（个人）
-/// * `last_editor: User<'a>` - <User> 最后修改人
+/// * `content_updated_at: Option<DateTime<Local>>` - 文档内容更新时间，从未编辑过内容的文档为 null
+/// * `book: Option<RepoListItem<'a>>` - <Repo> 所属知识库，知识库已被删除时为空
+/// * `user: Option<User<'a>>` - <User> 所属团队（个人），系统文档等情况下为空
+/// * `last_editor: Option<User<'a>>` - <User> 最后修改人，修改人已被移除时为空
+/// * `deleted_at: Option<DateTime<Local>>` - 删除时间，只有回收站中的文档才有
 /// * `created_at: DateTime<Local>` - 创建时间
 /// * `updated_at: DateTime<Local>` - 更新时间
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct DocListItem<'a> {
     pub id: i32,
     pub slug: Cow<'a, str>,
@@ -38,21 +59,51 @@ pub struct DocListItem<'a> {
     pub format: YuqueFormat,
     #[serde(with = "number_to_bool")]
     pub public: bool,
-    #[serde(with = "number_to_bool")]
-    pub status: bool,
+    pub status: DocStatus,
+    #[serde(default)]
     pub likes_count: u16,
+    #[serde(default)]
     pub comments_count: u16,
-    #[serde(with = "time_serde")]
-    pub content_updated_at: DateTime<Local>,
+    #[serde(default, with = "option_time_serde")]
+    pub content_updated_at: Option<DateTime<Local>>,
     pub book: Option<RepoListItem<'a>>,
     pub user: Option<User<'a>>,
-    pub last_editor: User<'a>,
+    pub last_editor: Option<User<'a>>,
+    #[serde(default, with = "option_time_serde")]
+    pub deleted_at: Option<DateTime<Local>>,
     #[serde(with = "time_serde")]
     pub created_at: DateTime<Local>,
     #[serde(with = "time_serde")]
     pub updated_at: DateTime<Local>,
 }
 
+/// 只按 `id` 计算哈希，见 [`User`] 的说明
+impl<'a> Hash for DocListItem<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// 一行摘要：`title (slug) — updated 2023-03-01 16:00`
+///
+/// 时间取文档内容的更新时间，从未编辑过内容时取 `updated_at`；标题为空时显示 `untitled`。
+impl<'a> Display for DocListItem<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = match self.title.trim() {
+            "" => "untitled",
+            title => title,
+        };
+        let updated_at = self.content_updated_at.unwrap_or(self.updated_at);
+
+        write!(
+            f,
+            "{title} ({}) — updated {}",
+            self.slug,
+            updated_at.format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
 /// DocDetail
 /// 文档详情
 ///
@@ -71,14 +122,16 @@ pub struct DocListItem<'a> {
 /// * `body_lake: Cow<'a, str>` - 语雀 lake 格式的文档内容
 /// * `creator_id: i32` - 文档创建人 User Id
 /// * `public: bool` - 公开级别 [0 - 私密, 1 - 公开]
-/// * `status: bool` - 状态 [0 - 草稿, 1 - 正常]
-/// * `likes_count: u16` - 喜欢数量
-/// * `comments_count: u16` - 评论数量
+/// * `status: DocStatus` - 状态 [0 - 草稿, 1 - 正常]
+/// * `likes_count: u16` - 喜欢数量，没有返回时为 0
+/// * `comments_count: u16` - 评论数量，没有返回时为 0
 /// * `content_updated_at: DateTime<Local>` - 文档内容更新时间
 /// * `deleted_at: Option<DateTime<Local>>` - 删除时间，未删除为 null
 /// * `created_at: DateTime<Local>` - 创建时间
 /// * `updated_at: DateTime<Local>` - 更新时间
-#[derive(Deserialize, Debug)]
+/// * `hits: Option<u32>` - 阅读量，通过 `optionalProperties` 请求时才返回
+/// * `tags: Option<Vec<Cow<'a, str>>>` - 标签，通过 `optionalProperties` 请求时才返回
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct DocDetail<'a> {
     pub id: i32,
     pub slug: Cow<'a, str>,
@@ -95,10 +148,11 @@ pub struct DocDetail<'a> {
     pub creator_id: Option<i32>,
     #[serde(with = "number_to_bool")]
     pub public: bool,
-    #[serde(with = "number_to_bool")]
-    pub status: bool,
-    pub likes_count: Option<u16>,
-    pub comments_count: Option<u16>,
+    pub status: DocStatus,
+    #[serde(default)]
+    pub likes_count: u16,
+    #[serde(default)]
+    pub comments_count: u16,
     #[serde(with = "time_serde")]
     pub content_updated_at: DateTime<Local>,
     #[serde(with = "option_time_serde")]
@@ -107,88 +161,97 @@ pub struct DocDetail<'a> {
     pub created_at: DateTime<Local>,
     #[serde(with = "time_serde")]
     pub updated_at: DateTime<Local>,
+    #[serde(default)]
+    pub hits: Option<u32>,
+    #[serde(default)]
+    pub tags: Option<Vec<Cow<'a, str>>>,
 }
 
-/// 用于post的文档
+/// 文档的历史版本
 ///
 /// # Fields
-///
-/// * `title: String` - 标题
-/// * `slug: String` - 文档 Slug
-/// * `format: YuqueFormat` - 支持 markdown、lake、html，默认为 markdown
-/// * `body: String` - format 描述的正文内容，最大允许 5MB
-#[derive(Builder, Serialize, Deserialize, Clone, Default, Debug)]
-pub struct Doc {
-    pub title: String,
-    #[builder(default = "gen_random_slug(16)")]
-    pub slug: String,
-    #[builder(default)]
+/// * `id: i32` - 版本编号
+/// * `doc_id: i32` - 文档编号
+/// * `slug: Cow<'a, str>` - 文档路径
+/// * `title: Cow<'a, str>` - 标题
+/// * `format: YuqueFormat` - 正文的格式
+/// * `body: Cow<'a, str>` - 正文 Markdown 源代码
+/// * `created_at: DateTime<Local>` - 创建时间
+/// * `updated_at: DateTime<Local>` - 更新时间
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DocVersionDetail<'a> {
+    pub id: i32,
+    pub doc_id: i32,
+    pub slug: Cow<'a, str>,
+    pub title: Cow<'a, str>,
     pub format: YuqueFormat,
-    #[builder(default)]
-    pub body: String,
+    pub body: Cow<'a, str>,
+    #[serde(with = "time_serde")]
+    pub created_at: DateTime<Local>,
+    #[serde(with = "time_serde")]
+    pub updated_at: DateTime<Local>,
 }
 
-impl Doc {
-    /// 创建一个文档
-    pub fn builder() -> DocBuilder {
-        DocBuilder::default()
-    }
+/// 文档的历史版本列表项，不包含正文
+///
+/// # Fields
+/// * `id: i32` - 版本编号
+/// * `doc_id: i32` - 文档编号
+/// * `slug: Cow<'a, str>` - 文档路径
+/// * `title: Cow<'a, str>` - 标题
+/// * `user_id: Option<i32>` - 保存这个版本的用户编号
+/// * `created_at: DateTime<Local>` - 创建时间
+/// * `updated_at: DateTime<Local>` - 更新时间
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DocVersion<'a> {
+    pub id: i32,
+    pub doc_id: i32,
+    pub slug: Cow<'a, str>,
+    pub title: Cow<'a, str>,
+    #[serde(default)]
+    pub user_id: Option<i32>,
+    #[serde(with = "time_serde")]
+    pub created_at: DateTime<Local>,
+    #[serde(with = "time_serde")]
+    pub updated_at: DateTime<Local>,
 }
 
-impl<'a> TryFrom<DocDetail<'a>> for Doc {
-    type Error = YuqueError;
-
-    fn try_from(value: DocDetail<'a>) -> Result<Self, Self::Error> {
-        match value.format {
-            YuqueFormat::Markdown => (),
-            _ => return Err(YuqueError::NotSupportFormat(value.format.into())),
-        }
-
-        Ok(Doc {
-            title: value.title.into_owned(),
-            slug: value.slug.into_owned(),
-            format: value.format,
-            body: value.body.into_owned(),
-        })
-    }
-}
+/// 语雀图片、附件所在的 CDN 域名
+const YUQUE_ASSET_HOSTS: &[&str] = &["cdn.nlark.com", "cdn.yuque.com"];
 
-impl<'a> TryFrom<DocDetail<'a>> for (Doc, i32) {
-    type Error = YuqueError;
+/// 用于补全正文中相对地址的语雀域名
+const YUQUE_BASE_URL: &str = "https://www.yuque.com";
 
-    fn try_from(value: DocDetail<'a>) -> Result<Self, Self::Error> {
-        match value.format {
-            YuqueFormat::Markdown => (),
-            _ => return Err(YuqueError::NotSupportFormat(value.format.into())),
-        }
+impl<'a> DocDetail<'a> {
+    /// Get the body rendered in the given format
+    /// 获取指定格式的正文
+    ///
+    /// 格式与字段的对应关系：
+    /// * `YuqueFormat::Markdown` - `body`（需要带上 `raw=1` 请求才是 Markdown 源码）
+    /// * `YuqueFormat::Html` - `body_html`
+    /// * `YuqueFormat::Lake` - `body_lake`
+    ///
+    /// 对应字段没有返回时得到 `YuqueError::NotSupportFormat`，而不是空值。
+    pub fn body_as(&self, format: YuqueFormat) -> Result<&str, YuqueError> {
+        let body = match format {
+            YuqueFormat::Markdown => Some(&self.body),
+            YuqueFormat::Html => self.body_html.as_ref(),
+            YuqueFormat::Lake => self.body_lake.as_ref(),
+        };
 
-        Ok((
-            Doc {
-                title: value.title.into_owned(),
-                slug: value.slug.into_owned(),
-                format: value.format,
-                body: value.body.into_owned(),
-            },
-            value.id,
-        ))
+        body.map(|body| body.as_ref())
+            .ok_or_else(|| YuqueError::NotSupportFormat(format.into()))
     }
-}
-
-#[derive(Debug)]
-pub struct DocsClient {
-    pub(crate) client: Yuque,
-}
 
-impl DocsClient {
-    /// List the documents of a repository
-    /// 获取仓库下的文档列表
+    /// Collect the urls of images/attachments referenced by the body
+    /// 获取正文中引用的语雀图片/附件地址
     ///
-    /// # Arguments
-    /// * `namespace: impl ToString` - 仓库的命名空间/id
+    /// 会扫描 Markdown 图片、链接以及内联的 `<img>` 标签，只保留指向语雀资源域名
+    /// （或 `yuque.com/attachments/`）的地址。相对地址按 `https://www.yuque.com` 补全，
+    /// `#` 之后的片段会被去掉，结果按正文中出现的顺序去重。
     ///
     /// # Example
-    /// ```rust
-    ///
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -198,37 +261,52 @@ impl DocsClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let docs = yuque.docs().list_by_repo_namespace("your namespace").await?;
+    ///     let docs = yuque.docs();
     ///
-    ///     println!("{:?}", docs);
+    ///     let doc = docs.get_with_repo_ns("your namespace", "your slug", Some(&[("raw", "1")])).await?;
+    ///
+    ///     println!("{:?}", doc.data.attachment_urls());
     ///     Ok(())
     /// }
-    ///
     /// ```
-    pub async fn list_with_repo(
-        &self,
-        namespace: impl ToString,
-    ) -> Result<YuqueResponse<Vec<DocListItem>>, YuqueError> {
-        let url = format!("/repos/{}/docs", namespace.to_string());
+    pub fn attachment_urls(&self) -> Vec<String> {
+        let mut targets = markdown_link_targets(&self.body);
+        targets.extend(html_img_sources(&self.body));
+        targets.sort_by_key(|(position, _)| *position);
 
-        let response = self.client.get(&url)?.send().await?;
+        let mut urls: Vec<String> = Vec::new();
 
-        judge_status_code(response.status().as_u16(), url)?;
+        for url in targets
+            .into_iter()
+            .filter_map(|(_, target)| resolve_asset_url(target))
+        {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
 
-        Ok(response.json().await?)
+        urls
     }
 
-    /// Get a document
-    /// 获取文档详情
+    /// Check whether the doc has the same content as a local [`Doc`]
+    /// 判断文档内容是否与本地的 [`Doc`] 相同，忽略时间、计数等易变字段
+    ///
+    /// 比较规则：
+    /// * `title`、`slug` 去掉首尾空白后比较
+    /// * `format` 必须相同
+    /// * 正文取 `doc.format` 对应的字段（见 [`DocDetail::body_as`]），没有该格式的正文时视为不同；
+    ///   比较前统一把 `\r\n` 换成 `\n`，并去掉末尾的空白和换行
+    ///
+    /// 同步工具可以用它跳过没有变化的更新，避免无意义的写请求和版本记录。
+    /// 注意：只有带上 `raw=1` 请求到的 `body` 才是 Markdown 源码。
     ///
     /// # Arguments
-    /// * `namespace: impl ToString` - 仓库的命名空间
-    /// * `slug: impl ToString` - 文档的 Slug
-    /// * `data: Option<Vec<(String, String)>>` - 查询参数
+    ///
+    /// * `doc` - 本地的文档
     ///
     /// # Example
-    /// ```rust
-    /// use yuque_rust::Yuque;
+    /// ```rust,no_run
+    /// use yuque_rust::{Doc, Yuque};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -237,39 +315,42 @@ impl DocsClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let doc = yuque.docs().get_with_repo_ns("your namespace", "your slug", None).await?;
+    ///     let docs = yuque.docs();
     ///
-    ///     println!("{:?}", doc);
+    ///     let local = Doc::builder()
+    ///                     .title("your title".into())
+    ///                     .slug("your slug".into())
+    ///                     .body("your body".into())
+    ///                     .build()?;
+    ///
+    ///     let remote = docs.get_with_repo_ns("your namespace", "your slug", Some(&[("raw", "1")])).await?;
+    ///
+    ///     if !remote.data.content_equals(&local) {
+    ///         docs.update_with_repo("your namespace", remote.data.id, local).await?;
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_with_repo_ns(
-        &self,
-        namespace: impl ToString,
-        slug: impl ToString,
-        data: Option<&[(&str, &str)]>,
-    ) -> Result<YuqueResponse<DocDetail>, YuqueError> {
-        let url = format!("/repos/{}/docs/{}", namespace.to_string(), slug.to_string());
-
-        let data = data.unwrap_or_default();
-
-        let response = self.client.get(&url)?.query(&data).send().await?;
-
-        judge_status_code(response.status().as_u16(), url)?;
+    pub fn content_equals(&self, doc: &Doc) -> bool {
+        let Ok(body) = self.body_as(doc.format) else {
+            return false;
+        };
 
-        Ok(response.json().await?)
+        self.title.trim() == doc.title.trim()
+            && self.slug.trim() == doc.slug.trim()
+            && self.format == doc.format
+            && normalize_body(body) == normalize_body(&doc.body)
     }
 
-    /// Create a document
-    /// 创建文档
+    /// Count the words of the markdown body
+    /// 统计 Markdown 正文的字数
     ///
-    /// # Arguments
-    /// * `namespace: impl ToString` - 仓库的命名空间/id
-    /// * `data: Option<Doc>` - 文档数据
+    /// 中日韩文字每个字算一个字，其余连续的字母/数字算一个词，标点和 Markdown 语法符号不计入。
+    /// 只在本地计算已获取到的 `body`，不会发出请求。
     ///
     /// # Example
-    /// ```rust
-    /// use yuque_rust::{Yuque, Doc};
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -278,42 +359,46 @@ impl DocsClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let doc = Doc::builder()
-    ///                     .title("title")
-    ///                     .body("body")
-    ///                     .build();
+    ///     let docs = yuque.docs();
     ///
-    ///     let doc = yuque.docs().create_with_repo("your namespace", Some(doc)).await?;
+    ///     let doc = docs.get_with_repo_ns("your namespace", "your slug", Some(&[("raw", "1")])).await?.data;
     ///
-    ///     println!("{:?}", doc);
+    ///     println!("{} words, {:?} to read", doc.word_count(), doc.reading_time());
     ///     Ok(())
     /// }
-    pub async fn create_with_repo(
-        &self,
-        namespace: impl ToString,
-        data: Doc,
-    ) -> Result<YuqueResponse<DocDetail>, YuqueError> {
-        let url = format!("/repos/{}/docs", namespace.to_string());
-
-        let data = serde_json::to_string(&data).ok();
-
-        let response = self.client.post(&url, data)?.send().await?;
-
-        judge_status_code(response.status().as_u16(), url)?;
+    /// ```
+    pub fn word_count(&self) -> usize {
+        let (cjk, words) = count_words(&self.body);
+        cjk + words
+    }
 
-        Ok(response.json().await?)
+    /// Estimate the reading time of the markdown body
+    /// 估算正文的阅读时间
+    ///
+    /// 按每分钟 300 个中日韩文字、200 个其他语言的词计算，计数规则同 [`DocDetail::word_count`]。
+    pub fn reading_time(&self) -> Duration {
+        let (cjk, words) = count_words(&self.body);
+
+        Duration::from_millis(
+            (cjk as u64).saturating_mul(CJK_CHAR_MILLIS)
+                + (words as u64).saturating_mul(WORD_MILLIS),
+        )
     }
+}
 
-    /// delete a document
-    /// 删除文档
+#[cfg(feature = "render")]
+impl<'a> DocDetail<'a> {
+    /// Render the markdown body to html locally
+    /// 在本地把 Markdown 正文转换为 HTML
     ///
-    /// # Arguments
-    /// * `namespace: impl Into<String>` - 仓库的命名空间/id
-    /// * `slug: impl Into<String>` - 文档的 Slug
+    /// `body_html` 只有服务端返回时才有，且已不推荐使用，这里用 `pulldown-cmark` 在本地转换，
+    /// 结果不依赖服务端的行为。支持代码块、表格、删除线和任务列表。
+    /// 只有带上 `raw=1` 请求到的 `body` 才是 Markdown 源码。
     ///
-    /// # Example
+    /// 需要开启 `render` feature。
     ///
-    /// ```rust
+    /// # Example
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -323,197 +408,3680 @@ impl DocsClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let doc = yuque.docs().delete_with_repo("your namespace", "your slug").await?;
+    ///     let docs = yuque.docs();
     ///
-    ///     println!("{:?}", doc);
+    ///     let doc = docs.get_with_repo_ns("your namespace", "your slug", Some(&[("raw", "1")])).await?.data;
+    ///
+    ///     println!("{}", doc.render_html());
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_with_repo(
-        &self,
-        namespace: impl ToString,
-        id: i32,
-    ) -> Result<YuqueResponse<DocDetail>, YuqueError> {
-        let url = format!("/repos/{}/docs/{}", namespace.to_string(), id);
+    pub fn render_html(&self) -> String {
+        use pulldown_cmark::{html, Options, Parser};
 
-        let response = self.client.delete(&url)?.send().await?;
+        let options =
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let mut rendered = String::with_capacity(self.body.len() * 3 / 2);
+        html::push_html(&mut rendered, Parser::new_ext(&self.body, options));
 
-        Ok(response.json().await?)
+        rendered
     }
+}
 
-    /// Update a document
-    /// 更新文档
-    ///
-    /// # Arguments
-    /// * `namespace: impl Into<String>` - 仓库的命名空间/id
-    /// * `slug: impl Into<String>` - 文档的 Slug
-    /// * `data: Option<Doc>` - 文档数据
-    ///
-    /// # Example
-    /// ```rust
-    /// use yuque_rust::{Yuque, Doc};
-    ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let yuque = Yuque::builder()
-    ///                         .token("your token".to_string())
-    ///                         .host("https://www.yuque.com".to_string())  
-    ///                         .build()?;
-    ///     
-    ///     let doc = Doc::builder()
-    ///                    .title("title")
-    ///                     .body("body")
-    ///                     .build();
-    ///
-    ///     let doc = yuque.docs().update_with_repo("your namespace", "doc id", Some(doc)).await?;
-    ///
-    ///     println!("{:?}", doc);
-    ///     Ok(())
-    /// }
-    pub async fn update_with_repo(
-        &self,
-        namespace: impl ToString,
-        id: i32,
-        data: Doc,
-    ) -> Result<YuqueResponse<DocDetail>, YuqueError> {
-        match data.format {
-            YuqueFormat::Markdown => (),
-            _ => return Err(YuqueError::NotSupportFormat(data.format.into())),
-        }
+/// 阅读一个中日韩文字的时间，每分钟 300 字
+const CJK_CHAR_MILLIS: u64 = 60_000 / 300;
+/// 阅读一个其他语言的词的时间，每分钟 200 词
+const WORD_MILLIS: u64 = 60_000 / 200;
+
+/// 中日韩文字（含假名和谚文）按字计数
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // 平假名、片假名
+        | '\u{3400}'..='\u{4DBF}' // 汉字扩展 A
+        | '\u{4E00}'..='\u{9FFF}' // 汉字
+        | '\u{AC00}'..='\u{D7AF}' // 谚文
+        | '\u{F900}'..='\u{FAFF}' // 兼容汉字
+        | '\u{20000}'..='\u{2FA1F}' // 汉字扩展 B 及以后
+    )
+}
 
-        let url = format!("/repos/{}/docs/{}", namespace.to_string(), id);
+/// 返回（中日韩文字数，其他词数）
+fn count_words(body: &str) -> (usize, usize) {
+    let (mut cjk, mut words) = (0, 0);
+    let mut in_word = false;
+
+    for c in body.chars() {
+        if is_cjk(c) {
+            cjk += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() || (in_word && matches!(c, '\'' | '’')) {
+            if !in_word {
+                words += 1;
+            }
+            in_word = true;
+        } else {
+            in_word = false;
+        }
+    }
 
-        let data = serde_json::to_string(&data).ok();
+    (cjk, words)
+}
 
-        let response = self.client.put(&url, data)?.send().await?;
+/// 统一换行符并去掉末尾的空白，用于比较正文
+fn normalize_body(body: &str) -> String {
+    body.replace("\r\n", "\n").trim_end().to_string()
+}
 
-        judge_status_code(response.status().as_u16(), url)?;
+/// `[text](url)` / `![alt](url "title")` 中的 url 及其位置
+fn markdown_link_targets(body: &str) -> Vec<(usize, &str)> {
+    let mut targets = Vec::new();
+
+    for (position, _) in body.match_indices("](") {
+        let start = position + 2;
+        let rest = &body[start..];
+
+        let target = if let Some(rest) = rest.strip_prefix('<') {
+            rest.find('>').map(|end| &rest[..end])
+        } else {
+            let mut depth = 0;
+            let end = rest
+                .char_indices()
+                .find(|(_, c)| match c {
+                    '(' => {
+                        depth += 1;
+                        false
+                    }
+                    ')' if depth == 0 => true,
+                    ')' => {
+                        depth -= 1;
+                        false
+                    }
+                    c => c.is_whitespace(),
+                })
+                .map(|(end, _)| end);
+
+            end.map(|end| &rest[..end])
+        };
 
-        Ok(response.json().await?)
+        if let Some(target) = target.filter(|target| !target.is_empty()) {
+            targets.push((start, target));
+        }
     }
+
+    targets
 }
 
-#[cfg(test)]
-mod test {
-    use std::{error::Error, ops::Not};
+/// 内联 `<img src="url">` 中的 url 及其位置
+fn html_img_sources(body: &str) -> Vec<(usize, &str)> {
+    let lowercase = body.to_ascii_lowercase();
+    let mut sources = Vec::new();
 
-    use crate::{Doc, Yuque};
+    for (position, _) in lowercase.match_indices("<img") {
+        let end = lowercase[position..]
+            .find('>')
+            .map_or(body.len(), |end| position + end);
 
-    macro_rules! aw {
-        ($e:expr) => {
-            tokio_test::block_on($e)
+        let Some(attr) = lowercase[position..end].find("src=") else {
+            continue;
         };
-    }
-
-    const TEST_NS: &str = "lzzzt/sdk-test";
-    const TEST_HOST: &str = "https://lzzzt.yuque.com/api/v2";
-
-    #[test]
-    fn should_list_docs() -> Result<(), Box<dyn Error>> {
-        dotenv::from_path(".env.dev").ok();
-
-        let token = std::env::var("TOKEN")?;
 
-        let client = Yuque::builder()
-            .token(token)
-            .host(TEST_HOST.into())
-            .build()?
-            .docs();
+        let start = position + attr + 4;
+        let value = &body[start..end];
 
-        let docs = aw!(client.list_with_repo(TEST_NS))?;
+        let source = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value
+                .split_whitespace()
+                .next()
+                .map(|source| source.trim_end_matches('/')),
+        };
 
-        assert!(docs.data.is_empty().not());
+        if let Some(source) = source.filter(|source| !source.is_empty()) {
+            sources.push((start, source));
+        }
+    }
 
-        let count = docs
-            .into_iter()
-            .map(|doc| doc.title)
-            .filter(|title| title.to_ascii_lowercase().contains("test"))
-            .count();
+    sources
+}
 
-        assert!(count > 0);
+/// 补全相对地址，并过滤掉不属于语雀资源的地址
+fn resolve_asset_url(target: &str) -> Option<String> {
+    let target = target.split('#').next()?.trim();
 
-        Ok(())
+    let url = if target.starts_with("//") {
+        Url::parse(&format!("https:{target}"))
+    } else if target.starts_with('/') {
+        Url::parse(YUQUE_BASE_URL).and_then(|base| base.join(target))
+    } else {
+        Url::parse(target)
     }
+    .ok()?;
 
-    #[test]
-    fn should_get_doc_detail() -> Result<(), Box<dyn Error>> {
-        dotenv::from_path(".env.dev").ok();
+    let host = url.host_str()?;
 
-        let token = std::env::var("TOKEN")?;
+    // 只接受 yuque.com 和它的子域名，`evilyuque.com` 这样的域名不算
+    let is_yuque = host == "yuque.com" || host.ends_with(".yuque.com");
 
-        let client = Yuque::builder()
-            .token(token)
-            .host(TEST_HOST.into())
-            .build()?
-            .docs();
+    let is_asset =
+        YUQUE_ASSET_HOSTS.contains(&host) || (is_yuque && url.path().starts_with("/attachments/"));
 
-        let doc =
-            aw!(client.get_with_repo_ns(TEST_NS, "create-by-sdk", Some(&[("raw", "1")])))?.data;
+    is_asset.then(|| url.to_string())
+}
 
-        assert!(doc
-            .body
-            .contains("This sentence is created by yuque-rust sdk."));
+/// 用于post的文档
+///
+/// # Fields
+///
+/// * `title: String` - 标题
+/// * `slug: String` - 文档 Slug
+/// * `format: YuqueFormat` - 支持 markdown、lake、html，默认为 markdown
+/// * `body: String` - format 描述的正文内容，最大允许 5MB
+/// * `public: Option<Visibility>` - 公开级别，不设置则不修改
+/// * `status: Option<DocStatus>` - 状态，草稿或发布，不设置则不修改
+#[derive(Builder, Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Doc {
+    pub title: String,
+    #[builder(default = "gen_random_slug(DOC_SLUG_LEN)")]
+    pub slug: String,
+    #[builder(default)]
+    pub format: YuqueFormat,
+    #[builder(default)]
+    pub body: String,
+    #[builder(default, setter(strip_option))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public: Option<Visibility>,
+    #[builder(default, setter(strip_option))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<DocStatus>,
+}
 
-        Ok(())
+impl DocBuilder {
+    /// 用给定的策略生成 slug，见 [`SlugGenerator`]
+    pub fn slug_with(&mut self, generator: &mut impl SlugGenerator) -> &mut Self {
+        self.slug = Some(generator.generate(DOC_SLUG_LEN));
+        self
     }
+}
+
+impl Doc {
+    /// 创建一个文档
+    pub fn builder() -> DocBuilder {
+        DocBuilder::default()
+    }
+
+    /// Make sure the doc is in markdown
+    /// 检查文档是否为 Markdown 格式
+    ///
+    /// 语雀的创建、更新接口支持 `markdown`、`lake`、`html` 三种格式，
+    /// 需要只处理 Markdown 的调用方可以在提交前用它校验。
+    ///
+    /// # Example
+    /// ```rust
+    /// use yuque_rust::{Doc, YuqueFormat};
+    ///
+    /// let doc = Doc {
+    ///     format: YuqueFormat::Lake,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(doc.ensure_markdown().is_err());
+    /// ```
+    pub fn ensure_markdown(&self) -> Result<(), YuqueError> {
+        match self.format {
+            YuqueFormat::Markdown => Ok(()),
+            _ => Err(YuqueError::NotSupportFormat(self.format.into())),
+        }
+    }
+}
+
+/// 用于部分更新的文档，只会提交设置了的字段
+///
+/// # Fields
+///
+/// * `title: Option<String>` - 标题
+/// * `slug: Option<String>` - 文档 Slug
+/// * `format: Option<YuqueFormat>` - 正文的格式
+/// * `body: Option<String>` - format 描述的正文内容
+/// * `public: Option<Visibility>` - 公开级别
+/// * `status: Option<DocStatus>` - 状态，草稿或发布
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::UpdateDoc;
+///
+/// let update = UpdateDoc::builder().title("x").build().unwrap();
+///
+/// assert_eq!(serde_json::to_string(&update).unwrap(), r#"{"title":"x"}"#);
+/// ```
+#[derive(Builder, Serialize, Clone, Default, Debug, PartialEq, Eq)]
+#[builder(default, setter(into, strip_option))]
+pub struct UpdateDoc {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<YuqueFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<DocStatus>,
+}
+
+impl UpdateDoc {
+    /// 创建一个部分更新
+    pub fn builder() -> UpdateDocBuilder {
+        UpdateDocBuilder::default()
+    }
+}
+
+/// 完整的文档会更新所有字段
+impl From<Doc> for UpdateDoc {
+    fn from(value: Doc) -> Self {
+        UpdateDoc {
+            title: Some(value.title),
+            slug: Some(value.slug),
+            format: Some(value.format),
+            body: Some(value.body),
+            public: value.public,
+            status: value.status,
+        }
+    }
+}
+
+/// 文档的状态
+///
+/// 以数字序列化：`0` - 草稿，`1` - 正常（已发布），其他数字在反序列化时会报错。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::DocStatus;
+///
+/// assert_eq!(serde_json::from_str::<DocStatus>("0").unwrap(), DocStatus::Draft);
+/// assert_eq!(serde_json::to_string(&DocStatus::Normal).unwrap(), "1");
+/// assert!(serde_json::from_str::<DocStatus>("2").is_err());
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum DocStatus {
+    Draft,
+    #[default]
+    Normal,
+}
+
+impl DocStatus {
+    /// 是否为草稿
+    pub fn is_draft(&self) -> bool {
+        matches!(self, DocStatus::Draft)
+    }
+
+    /// 是否已发布
+    pub fn is_published(&self) -> bool {
+        matches!(self, DocStatus::Normal)
+    }
+}
+
+impl TryFrom<u8> for DocStatus {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DocStatus::Draft),
+            1 => Ok(DocStatus::Normal),
+            _ => Err(format!(
+                "invalid doc status {value}, expected 0 (draft) or 1 (normal)"
+            )),
+        }
+    }
+}
+
+impl From<DocStatus> for u8 {
+    fn from(value: DocStatus) -> Self {
+        match value {
+            DocStatus::Draft => 0,
+            DocStatus::Normal => 1,
+        }
+    }
+}
+
+/// 正文取自 `format` 对应的字段（见 `DocDetail::body_as`），该字段没有返回时转换失败
+impl<'a> TryFrom<&DocDetail<'a>> for Doc {
+    type Error = YuqueError;
+
+    fn try_from(value: &DocDetail<'a>) -> Result<Self, Self::Error> {
+        Ok(Doc {
+            title: value.title.to_string(),
+            slug: value.slug.to_string(),
+            format: value.format,
+            body: value.body_as(value.format)?.to_string(),
+            public: Some(if value.public {
+                Visibility::Public
+            } else {
+                Visibility::Private
+            }),
+            status: Some(value.status),
+        })
+    }
+}
+
+impl<'a> TryFrom<DocDetail<'a>> for Doc {
+    type Error = YuqueError;
+
+    fn try_from(value: DocDetail<'a>) -> Result<Self, Self::Error> {
+        Doc::try_from(&value)
+    }
+}
+
+impl<'a> TryFrom<DocDetail<'a>> for (Doc, i32) {
+    type Error = YuqueError;
+
+    fn try_from(value: DocDetail<'a>) -> Result<Self, Self::Error> {
+        Ok((Doc::try_from(&value)?, value.id))
+    }
+}
+
+/// 文档的分享链接
+///
+/// # Fields
+/// * `url: Cow<'a, str>` - 分享链接
+/// * `password: Option<Cow<'a, str>>` - 访问密码，未设置为 null
+/// * `expires_at: Option<DateTime<Local>>` - 过期时间，永久有效为 null
+#[derive(Deserialize, Debug)]
+pub struct ShareLink<'a> {
+    pub url: Cow<'a, str>,
+    #[serde(default)]
+    pub password: Option<Cow<'a, str>>,
+    #[serde(default, with = "option_time_serde")]
+    pub expires_at: Option<DateTime<Local>>,
+}
+
+/// 创建分享链接的选项
+///
+/// # Fields
+/// * `password: Option<String>` - 访问密码，不设置则无需密码
+/// * `expires_at: Option<DateTime<Local>>` - 过期时间，不设置则永久有效
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::ShareOptions;
+///
+/// let options = ShareOptions {
+///     password: Some("123456".into()),
+///     ..Default::default()
+/// };
+///
+/// assert!(options.expires_at.is_none());
+/// ```
+#[derive(Builder, Serialize, Clone, Default, Debug)]
+pub struct ShareOptions {
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Local>>,
+}
+
+impl ShareOptions {
+    /// 创建分享链接的选项
+    pub fn builder() -> ShareOptionsBuilder {
+        ShareOptionsBuilder::default()
+    }
+}
+
+/// 移动文档的目标仓库，命名空间和编号分别对应不同的字段
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum MoveTarget {
+    TargetNamespace(String),
+    TargetBookId(i32),
+}
+
+impl From<RepoRef> for MoveTarget {
+    fn from(value: RepoRef) -> Self {
+        match value {
+            RepoRef::Namespace(namespace) => MoveTarget::TargetNamespace(namespace),
+            RepoRef::Id(id) => MoveTarget::TargetBookId(id),
+        }
+    }
+}
+
+/// 分页查询时单页的最大条数，超过的 `limit` 会被截断为该值
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// 分页查询的参数，`limit` 为 0 时报错，超过 [`MAX_PAGE_LIMIT`] 时截断
+pub(crate) fn page_query(
+    url: &str,
+    offset: u32,
+    limit: u32,
+) -> Result<[(&'static str, u32); 2], YuqueError> {
+    if limit == 0 {
+        return Err(YuqueError::invalid_params(
+            url,
+            "limit must be greater than 0",
+        ));
+    }
+
+    Ok([("offset", offset), ("limit", limit.min(MAX_PAGE_LIMIT))])
+}
+
+/// 文档列表、文档详情中可以额外请求的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocProperty {
+    Hits,
+    Tags,
+}
+
+impl DocProperty {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DocProperty::Hits => "hits",
+            DocProperty::Tags => "tags",
+        }
+    }
+}
+
+/// `optionalProperties` 参数的值，以逗号分隔
+fn join_properties(properties: &[DocProperty]) -> String {
+    properties
+        .iter()
+        .map(DocProperty::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 获取文档列表的查询参数
+///
+/// # Fields
+/// * `offset: Option<u32>` - 跳过的文档数
+/// * `limit: Option<u32>` - 本页的文档数，超过 [`MAX_PAGE_LIMIT`] 时截断
+/// * `optional_properties: Vec<DocProperty>` - 额外返回的字段，对应 `optionalProperties`
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{DocListQuery, DocProperty, QueryParams};
+///
+/// let query = DocListQuery::new()
+///                 .offset(20)
+///                 .limit(10)
+///                 .with(DocProperty::Hits)
+///                 .with(DocProperty::Tags);
+///
+/// assert_eq!(
+///     QueryParams::from(query),
+///     QueryParams::from(&[("offset", "20"), ("limit", "10"), ("optionalProperties", "hits,tags")])
+/// );
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct DocListQuery {
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+    pub optional_properties: Vec<DocProperty>,
+}
+
+impl DocListQuery {
+    /// 创建文档列表的查询参数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 跳过前 `offset` 篇文档
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// 本页最多返回 `limit` 篇文档
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 额外请求一个字段，重复的字段会被忽略
+    pub fn with(mut self, property: DocProperty) -> Self {
+        if !self.optional_properties.contains(&property) {
+            self.optional_properties.push(property);
+        }
+        self
+    }
+}
+
+impl From<DocListQuery> for QueryParams {
+    fn from(value: DocListQuery) -> Self {
+        let mut query = QueryParams::new();
+
+        if let Some(offset) = value.offset {
+            query.push("offset", offset);
+        }
+
+        if let Some(limit) = value.limit {
+            query.push("limit", limit.min(MAX_PAGE_LIMIT));
+        }
+
+        if !value.optional_properties.is_empty() {
+            query.push(
+                "optionalProperties",
+                join_properties(&value.optional_properties),
+            );
+        }
+
+        query
+    }
+}
+
+/// 获取文档详情的查询参数
+///
+/// # Fields
+/// * `raw: bool` - 是否返回 Markdown 原文，对应 `raw=1`
+/// * `optional_properties: Vec<DocProperty>` - 额外返回的字段，对应 `optionalProperties`
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{DocDetailQuery, DocProperty, QueryParams};
+///
+/// let query = DocDetailQuery::new()
+///                 .raw()
+///                 .with(DocProperty::Hits)
+///                 .with(DocProperty::Tags);
+///
+/// assert_eq!(
+///     QueryParams::from(query),
+///     QueryParams::from(&[("raw", "1"), ("optionalProperties", "hits,tags")])
+/// );
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct DocDetailQuery {
+    pub raw: bool,
+    pub optional_properties: Vec<DocProperty>,
+}
+
+impl DocDetailQuery {
+    /// 创建文档详情的查询参数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回 Markdown 原文
+    pub fn raw(mut self) -> Self {
+        self.raw = true;
+        self
+    }
+
+    /// 额外请求一个字段，重复的字段会被忽略
+    pub fn with(mut self, property: DocProperty) -> Self {
+        if !self.optional_properties.contains(&property) {
+            self.optional_properties.push(property);
+        }
+        self
+    }
+}
+
+impl From<DocDetailQuery> for QueryParams {
+    fn from(value: DocDetailQuery) -> Self {
+        let mut query = QueryParams::new();
+
+        if value.raw {
+            query.push("raw", 1);
+        }
+
+        if !value.optional_properties.is_empty() {
+            query.push(
+                "optionalProperties",
+                join_properties(&value.optional_properties),
+            );
+        }
+
+        query
+    }
+}
+
+/// 导出仓库的结果
+///
+/// * `exported` - 导出成功的文档 slug
+/// * `failed` - 导出失败的文档 slug 以及对应的错误
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+pub struct ExportRepoReport {
+    pub exported: Vec<String>,
+    pub failed: Vec<(String, YuqueError)>,
+}
+
+/// slug 只能作为 `dir` 下的文件名，不能跳出导出目录
+#[cfg(not(target_arch = "wasm32"))]
+fn is_plain_file_stem(slug: &str) -> bool {
+    !slug.contains(['/', '\\'])
+        && !slug.contains("..")
+        && matches!(
+            Path::new(slug).components().collect::<Vec<_>>()[..],
+            [Component::Normal(_)]
+        )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn already_exists(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!(
+            "{} already exists, set overwrite to replace it",
+            path.display()
+        ),
+    )
+}
+
+/// 条件请求的依据，用于 [`DocsClient::get_if_modified`]
+///
+/// * `ETag` - 之前响应中的 `ETag`，以 `If-None-Match` 发送
+/// * `ModifiedSince` - 之前看到的更新时间，以 `If-Modified-Since` 发送
+///
+/// 从 `&DocDetail` 转换时使用它的 `updated_at`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Precondition {
+    ETag(String),
+    ModifiedSince(DateTime<Local>),
+}
+
+impl Precondition {
+    fn header(&self) -> (HeaderName, String) {
+        match self {
+            Precondition::ETag(etag) => (IF_NONE_MATCH, etag.clone()),
+            Precondition::ModifiedSince(time) => (
+                IF_MODIFIED_SINCE,
+                time.with_timezone(&Utc)
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+impl From<DateTime<Local>> for Precondition {
+    fn from(value: DateTime<Local>) -> Self {
+        Self::ModifiedSince(value)
+    }
+}
+
+impl<'a> From<&DocDetail<'a>> for Precondition {
+    fn from(value: &DocDetail<'a>) -> Self {
+        Self::ModifiedSince(value.updated_at)
+    }
+}
+
+/// `upsert` 的结果
+///
+/// * `doc` - 创建或更新后的文档
+/// * `created` - 为 `true` 时文档是新创建的，否则是更新了已有的文档
+#[derive(Debug)]
+pub struct UpsertedDoc<'a> {
+    pub doc: DocDetail<'a>,
+    pub created: bool,
+}
+
+/// 标题为空（或只有空白字符）的文档不能创建
+pub(crate) fn check_title(url: &str, doc: &Doc) -> Result<(), YuqueError> {
+    if doc.title.trim().is_empty() {
+        return Err(YuqueError::invalid_params(
+            url,
+            "the title of the doc must not be empty",
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct DocsClient {
+    pub(crate) client: Yuque,
+}
+
+impl DocsClient {
+    /// List the documents of a repository
+    /// 获取仓库下的文档列表
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    ///
+    /// 需要分页或额外字段时使用 [`DocsClient::list_with_repo_query`]。
+    ///
+    /// # Example
+    /// ```rust,no_run
+    ///
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let docs = docs.list_with_repo("your namespace").await?;
+    ///
+    ///     println!("{:?}", docs);
+    ///     Ok(())
+    /// }
+    ///
+    /// ```
+    pub async fn list_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        self.list_with_repo_query(namespace, None).await
+    }
+
+    /// List the documents of a repository with query params
+    /// 带查询参数获取仓库下的文档列表
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `query: impl Into<QueryParams>` - 查询参数，推荐使用 [`DocListQuery`]
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{DocListQuery, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let query = DocListQuery::new().limit(10);
+    ///
+    ///     let docs = docs.list_with_repo_query("your namespace", query).await?;
+    ///
+    ///     println!("{:?}", docs);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_with_repo_query(
+        &self,
+        namespace: impl Into<RepoRef>,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        let request = self.client.get(&url)?.query(&query.into());
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// List one page of the documents of a repository
+    /// 分页获取仓库下的文档列表
+    ///
+    /// `limit` 超过 [`MAX_PAGE_LIMIT`] 时会被截断，为 0 时返回 `YuqueError::InvalidParams`。
+    /// 服务端返回的总数可以通过 [`YuqueResponse::total`] 读取，用于自行驱动翻页。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `offset: u32` - 跳过的文档数
+    /// * `limit: u32` - 本页的文档数
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let page = docs.list_with_repo_paged("your namespace", 0, 20).await?;
+    ///
+    ///     println!("{} of {:?}", page.len(), page.total());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_with_repo_paged(
+        &self,
+        namespace: impl Into<RepoRef>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        let query = page_query(&url, offset, limit)?;
+
+        let request = self.client.get(&url)?.query(&query);
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Walk every document of a repository
+    /// 以流的方式遍历仓库下的所有文档
+    ///
+    /// 内部按 [`MAX_PAGE_LIMIT`] 一页调用 [`DocsClient::list_with_repo_paged`]，直到服务端返回空页为止。
+    /// 页面是惰性拉取的，只消费前几项时不会请求后面的分页；请求出错时会产出一个 `Err`，随后流结束。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let mut stream = Box::pin(docs.stream_all_docs("your namespace"));
+    ///
+    ///     while let Some(doc) = stream.next().await {
+    ///         println!("{}", doc?.title);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stream_all_docs(
+        &self,
+        namespace: impl Into<RepoRef>,
+    ) -> impl Stream<Item = Result<DocListItem<'_>, YuqueError>> + '_ {
+        let namespace: RepoRef = namespace.into();
+
+        stream::unfold(
+            (0, Vec::new().into_iter(), false),
+            move |(offset, mut batch, done)| {
+                let namespace = namespace.clone();
+
+                async move {
+                    if let Some(item) = batch.next() {
+                        return Some((Ok(item), (offset, batch, done)));
+                    }
+
+                    if done {
+                        return None;
+                    }
+
+                    match self
+                        .list_with_repo_paged(namespace, offset, MAX_PAGE_LIMIT)
+                        .await
+                    {
+                        Ok(page) => {
+                            let offset = offset + page.data.len() as u32;
+                            let mut batch = page.data.into_iter();
+
+                            batch.next().map(|item| (Ok(item), (offset, batch, false)))
+                        }
+                        Err(e) => Some((Err(e), (offset, Vec::new().into_iter(), true))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Count the documents of a repository
+    /// 获取仓库下的文档总数
+    ///
+    /// 只请求一条数据并读取服务端返回的 `meta.total`，不会拉取所有分页；
+    /// 服务端没有返回总数时为 `None`。
+    /// 仓库和团队列表见 [`crate::ReposClient::count_repo_of_user`]、
+    /// [`crate::ReposClient::count_repo_of_group`] 和 [`crate::GroupsClient::count_of_user`]。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let count = yuque.docs().count_with_repo("your namespace").await?;
+    ///
+    ///     println!("{:?}", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+    ) -> Result<Option<u32>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        let request = self
+            .client
+            .get(&url)?
+            .query(&[("offset", "0"), ("limit", "1")]);
+
+        let response = self.client.execute(request).await?;
+
+        let response: YuqueResponse<Vec<DocListItem>> = handle_response(response, url).await?;
+
+        Ok(response.total())
+    }
+
+    /// List the recently updated documents of a user
+    /// 获取用户最近更新的文档
+    ///
+    /// 请求 `/users/:login/recent-updated?type=Doc`，可以用来构建动态列表。
+    ///
+    /// # Arguments
+    /// * `user: impl ToString` - 用户名/id
+    /// * `data: impl Into<QueryParams>` - 额外的查询参数，如 `offset`、`limit`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let recent = docs.list_recent("lzzzt", &[("limit", "10")]).await?;
+    ///
+    ///     for doc in recent.iter() {
+    ///         println!("{}", doc.title);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_recent(
+        &self,
+        user: impl ToString,
+        data: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let url = format!("/users/{}/recent-updated", user.to_string());
+
+        let request = self
+            .client
+            .get(&url)?
+            .query(&[("type", "Doc")])
+            .query(&data.into());
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Get a document
+    /// 获取文档详情
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `data: Option<&[(&str, &str)]>` - 查询参数，如 `Some(&[("raw", "1")])`，不需要时传 `None`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.get_with_repo_ns("your namespace", "your slug", None).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_with_repo_ns(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        self.get_with_repo_ns_query(namespace, doc, data).await
+    }
+
+    /// Get a document with query params
+    /// 带查询参数获取文档详情
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `query: impl Into<QueryParams>` - 查询参数，推荐使用 [`DocDetailQuery`]
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{DocDetailQuery, DocProperty, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let query = DocDetailQuery::new().raw().with(DocProperty::Hits);
+    ///
+    ///     let doc = docs.get_with_repo_ns_query("your namespace", "your slug", query).await?;
+    ///
+    ///     println!("{:?}", doc.data.hits);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_with_repo_ns_query(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let (namespace, doc): (RepoRef, DocRef) = (namespace.into(), doc.into());
+        let url = namespace.doc_path(&doc);
+
+        let request = self.client.get(&url)?.query(&query.into());
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Get a document without parsing the body
+    /// 获取文档详情的原始响应
+    ///
+    /// 与 [`DocsClient::get_with_repo_ns`] 相同，但错误状态码之外不做解析，直接返回 `reqwest::Response`，
+    /// 可以自行读取响应头，或者用 `bytes_stream` 流式读取很大的文档。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `data: impl Into<QueryParams>` - 查询参数，不需要时传 `None`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let response = docs.get_with_repo_ns_raw("your namespace", "your slug", None).await?;
+    ///
+    ///     println!("{:?}", response.headers().get("etag"));
+    ///     println!("{}", response.text().await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_with_repo_ns_raw(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        data: impl Into<QueryParams>,
+    ) -> Result<Response, YuqueError> {
+        let (namespace, doc): (RepoRef, DocRef) = (namespace.into(), doc.into());
+        let url = namespace.doc_path(&doc);
+
+        let request = self.client.get(&url)?.query(&data.into());
+
+        let response = self.client.execute(request).await?;
+
+        ensure_success(response, url).await
+    }
+
+    /// Get many documents concurrently
+    /// 并发获取多篇文档的详情
+    ///
+    /// 同时最多发出 `concurrency` 个请求（为 0 时按 1 处理），结果与 `slugs` 的顺序一一对应。
+    /// 每篇文档的错误互不影响，不会让整批失败。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `slugs: &[&str]` - 文档的 Slug 列表
+    /// * `concurrency: usize` - 最大并发数
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     for doc in docs.get_many("your namespace", &["slug-a", "slug-b"], 4).await {
+    ///         match doc {
+    ///             Ok(doc) => println!("{}", doc.title),
+    ///             Err(e) => println!("{e}"),
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_many(
+        &self,
+        namespace: impl Into<RepoRef>,
+        slugs: &[&str],
+        concurrency: usize,
+    ) -> Vec<Result<DocDetail<'_>, YuqueError>> {
+        let namespace: RepoRef = namespace.into();
+        let namespace = &namespace;
+
+        stream::iter(slugs)
+            .map(|slug| async move {
+                self.get_with_repo_ns(namespace, *slug, None)
+                    .await
+                    .map(|response| response.data)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Get a document only if it changed
+    /// 只在文档有变化时获取文档详情
+    ///
+    /// 按 `precondition` 发送 `If-None-Match` 或 `If-Modified-Since`，
+    /// 服务端返回 304 时得到 `Ok(None)`，适合轮询同步时跳过没有变化的文档。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `precondition: impl Into<Precondition>` - 之前看到的 `ETag` 或更新时间，也可以直接传入 `&DocDetail`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let cached = docs.get_with_repo_ns("your namespace", "your slug", None).await?.data;
+    ///
+    ///     match docs.get_if_modified("your namespace", "your slug", &cached).await? {
+    ///         Some(doc) => println!("changed: {}", doc.data.title),
+    ///         None => println!("not modified"),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_if_modified(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        precondition: impl Into<Precondition>,
+    ) -> Result<Option<YuqueResponse<DocDetail<'_>>>, YuqueError> {
+        let (namespace, doc): (RepoRef, DocRef) = (namespace.into(), doc.into());
+        let url = namespace.doc_path(&doc);
+
+        let (name, value) = precondition.into().header();
+        let request = self.client.get(&url)?.header(name, value);
+
+        let response = self.client.execute(request).await?;
+
+        match handle_response(response, url).await {
+            Ok(response) => Ok(Some(response)),
+            Err(YuqueError::NotModified { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a document by its id
+    /// 通过文档编号获取文档详情
+    ///
+    /// 与 `get_with_repo_ns` 相同，适用于已经从 `DocListItem`/`TocDocItem` 拿到编号的情况，
+    /// 不需要再去解析 slug。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    /// * `data: Option<&[(&str, &str)]>` - 查询参数
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.get_by_id("your namespace", 1024, Some(&[("raw", "1")])).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_by_id(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        data: Option<&[(&str, &str)]>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        self.get_with_repo_ns(namespace, DocRef::Id(id), data).await
+    }
+
+    /// Get a document rendered in the given format
+    /// 获取指定格式的文档详情
+    ///
+    /// 语雀只支持 `markdown`、`html`、`lake` 三种格式，格式与 `DocDetail` 字段的对应关系
+    /// 见 `DocDetail::body_as`。请求 Markdown 时会自动带上 `raw=1`；
+    /// 如果返回的文档中没有对应格式的正文，会得到 `YuqueError::NotSupportFormat`。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `render_as: YuqueFormat` - 需要的正文格式
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{Yuque, YuqueFormat};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.get_as("your namespace", "your slug", YuqueFormat::Html).await?;
+    ///
+    ///     println!("{}", doc.data.body_as(YuqueFormat::Html)?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_as(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        render_as: YuqueFormat,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let data: &[(&str, &str)] = match render_as {
+            YuqueFormat::Markdown => &[("raw", "1")],
+            YuqueFormat::Html | YuqueFormat::Lake => &[],
+        };
+
+        let response = self.get_with_repo_ns(namespace, doc, Some(data)).await?;
+
+        response.data.body_as(render_as)?;
+
+        Ok(response)
+    }
+
+    /// Export a document to a markdown file
+    /// 将文档导出为本地的 Markdown 文件
+    ///
+    /// 以 `raw=1` 获取文档并把 `body` 写入 `path`，会自动创建上级目录。
+    /// 文件已存在且 `overwrite` 为 `false` 时不会发出请求，直接返回
+    /// `ErrorKind::AlreadyExists` 的 `YuqueError::Io`；其余的文件系统错误同样以 `YuqueError::Io` 返回。
+    /// 需要本地文件系统，wasm 下不可用。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: impl Into<DocRef>` - 文档的 Slug/id
+    /// * `path: impl AsRef<Path>` - 导出的文件路径
+    /// * `overwrite: bool` - 是否覆盖已存在的文件
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     docs.export_markdown("your namespace", "your slug", "backup/your-slug.md", false).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn export_markdown(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: impl Into<DocRef>,
+        path: impl AsRef<Path>,
+        overwrite: bool,
+    ) -> Result<(), YuqueError> {
+        let path = path.as_ref();
+
+        if !overwrite && fs::try_exists(path).await? {
+            return Err(already_exists(path).into());
+        }
+
+        let response = self
+            .get_with_repo_ns(namespace, doc, Some(&[("raw", "1")]))
+            .await?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .create_new(!overwrite)
+            .truncate(true)
+            .open(path)
+            .await
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::AlreadyExists => already_exists(path),
+                _ => e,
+            })?;
+
+        file.write_all(response.data.body.as_bytes()).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Export every document of a repository to markdown files
+    /// 将仓库下的所有文档导出为 `dir` 目录下的 `{slug}.md`
+    ///
+    /// 先通过 [`DocsClient::stream_all_docs`] 列出全部文档，再以最多 `concurrency` 个并发调用
+    /// [`DocsClient::export_markdown`]，已存在的文件会被覆盖。
+    /// 列出文档失败时直接返回错误；单篇文档失败不会中断整个过程，会记录在返回的 `ExportRepoReport` 中。
+    /// slug 不是单个普通的路径部分（含 `/`、`\`、`..` 或是绝对路径）的文档不会被写入，同样记录为失败。
+    /// `concurrency` 为 0 时返回 `YuqueError::InvalidParams`。与 `export_markdown` 一样，wasm 下不可用。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `dir: impl AsRef<Path>` - 导出的目录
+    /// * `concurrency: usize` - 同时导出的文档数
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let report = docs.export_repo("your namespace", "backup", 4).await?;
+    ///
+    ///     for (slug, e) in report.failed {
+    ///         println!("{slug}: {e}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn export_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        dir: impl AsRef<Path>,
+        concurrency: usize,
+    ) -> Result<ExportRepoReport, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let dir = dir.as_ref();
+
+        if concurrency == 0 {
+            return Err(YuqueError::invalid_params(
+                namespace.docs_path(),
+                "concurrency must be greater than 0",
+            ));
+        }
+
+        let slugs: Vec<String> = self
+            .stream_all_docs(&namespace)
+            .map_ok(|doc| doc.slug.into_owned())
+            .try_collect()
+            .await?;
+
+        let mut report = ExportRepoReport::default();
+
+        let (slugs, rejected): (Vec<_>, Vec<_>) =
+            slugs.into_iter().partition(|slug| is_plain_file_stem(slug));
+
+        for slug in rejected {
+            let e = YuqueError::invalid_params(
+                namespace.docs_path(),
+                format!("slug {slug:?} can not be used as a file name"),
+            );
+            report.failed.push((slug, e));
+        }
+
+        let mut results = stream::iter(slugs)
+            .map(|slug| {
+                let path = dir.join(format!("{slug}.md"));
+                let namespace = &namespace;
+
+                async move {
+                    let result = self
+                        .export_markdown(namespace, slug.as_str(), path, true)
+                        .await;
+                    (slug, result)
+                }
+            })
+            .buffer_unordered(concurrency);
+
+        while let Some((slug, result)) = results.next().await {
+            match result {
+                Ok(()) => report.exported.push(slug),
+                Err(e) => report.failed.push((slug, e)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Create a document
+    /// 创建文档
+    ///
+    /// 标题为空（或只有空白字符）时直接返回 `YuqueError::InvalidParams`，不会发出请求。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `data: Doc` - 文档数据
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{Yuque, Doc};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let doc = Doc::builder()
+    ///                     .title("title".into())
+    ///                     .body("body".into())
+    ///                     .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.create_with_repo("your namespace", doc).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        data: Doc,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.docs_path();
+
+        check_title(&url, &data)?;
+
+        let data = serde_json::to_string(&data).ok();
+
+        let request = self.client.post(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// delete a document
+    /// 删除文档
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.delete_with_repo("your namespace", 1024).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.doc_path(&DocRef::Id(id));
+
+        let request = self.client.delete(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Update a document
+    /// 更新文档
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    /// * `data: impl Into<UpdateDoc>` - 文档数据，传入 `Doc` 时更新所有字段，
+    ///   传入 `UpdateDoc` 时只更新设置了的字段
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{Yuque, Doc, UpdateDoc};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let doc = Doc::builder()
+    ///                     .title("title".into())
+    ///                     .body("body".into())
+    ///                     .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.update_with_repo("your namespace", 1024, doc).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///
+    ///     // 只修改标题，正文保持不变
+    ///     let rename = UpdateDoc::builder().title("new title").build()?;
+    ///
+    ///     let doc = docs.update_with_repo("your namespace", 1024, rename).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_with_repo(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        data: impl Into<UpdateDoc>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.doc_path(&DocRef::Id(id));
+
+        let data = serde_json::to_string(&data.into()).ok();
+
+        let request = self.client.put(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Create or update a document by its slug
+    /// 按 slug 创建或更新文档
+    ///
+    /// 先按 `doc.slug` 获取文档：存在时用它的 id 更新，返回 404 时创建。
+    /// 其他错误（包括获取时的权限错误）会直接返回，不会尝试创建。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `doc: Doc` - 文档，`slug` 用来查找已有的文档
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{Doc, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = Doc::builder()
+    ///                 .title("Synced".into())
+    ///                 .slug("synced".into())
+    ///                 .body("synced from a local folder".into())
+    ///                 .build()?;
+    ///
+    ///     let upserted = docs.upsert("your namespace", doc).await?;
+    ///
+    ///     println!("{} {}", upserted.doc.slug, upserted.created);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn upsert(
+        &self,
+        namespace: impl Into<RepoRef>,
+        doc: Doc,
+    ) -> Result<UpsertedDoc<'_>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+
+        match self
+            .get_with_repo_ns(&namespace, doc.slug.as_str(), None)
+            .await
+        {
+            Ok(existing) => {
+                let doc = self
+                    .update_with_repo(&namespace, existing.data.id, doc)
+                    .await?
+                    .data;
+
+                Ok(UpsertedDoc {
+                    doc,
+                    created: false,
+                })
+            }
+            Err(YuqueError::NotFound { .. }) => {
+                let doc = self.create_with_repo(&namespace, doc).await?.data;
+
+                Ok(UpsertedDoc { doc, created: true })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List the versions of a document
+    /// 获取文档的历史版本列表
+    ///
+    /// 历史版本只按文档 id 区分，所以不需要仓库的命名空间。
+    ///
+    /// # Arguments
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     for version in docs.list_versions(1024).await? {
+    ///         println!("{} {}", version.id, version.created_at);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_versions(
+        &self,
+        id: i32,
+    ) -> Result<YuqueResponse<Vec<DocVersion<'_>>>, YuqueError> {
+        let url = "/doc_versions".to_string();
+
+        let request = self.client.get(&url)?.query(&[("doc_id", id)]);
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Get a version of a document
+    /// 获取文档某个历史版本的内容
+    ///
+    /// 版本不属于 `id` 对应的文档时返回 `YuqueError::InvalidParams`。
+    ///
+    /// # Arguments
+    /// * `id: i32` - 文档的 id
+    /// * `version_id: i32` - 历史版本的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let version = docs.get_version(1024, 2048).await?;
+    ///
+    ///     println!("{}", version.data.body);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_version(
+        &self,
+        id: i32,
+        version_id: i32,
+    ) -> Result<YuqueResponse<DocVersionDetail<'_>>, YuqueError> {
+        let url = format!("/doc_versions/{}", version_id);
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        let version: YuqueResponse<DocVersionDetail> =
+            handle_response(response, url.clone()).await?;
+
+        if version.data.doc_id != id {
+            return Err(YuqueError::invalid_params(
+                url,
+                format!("version {version_id} does not belong to doc {id}"),
+            ));
+        }
+
+        Ok(version)
+    }
+
+    /// Diff two versions of a document
+    /// 比较文档的两个历史版本
+    ///
+    /// 同时获取两个版本的正文，返回从 `from_version` 到 `to_version` 的 unified diff，
+    /// 两个版本相同时返回空字符串。版本不存在时返回 `YuqueError::NotFound`，
+    /// 版本不属于 `id` 对应的文档时返回 `YuqueError::InvalidParams`（见 [`DocsClient::get_version`]）。
+    ///
+    /// 需要开启 `diff` feature。
+    ///
+    /// # Arguments
+    /// * `id: i32` - 文档的 id
+    /// * `from_version: i32` - 旧版本的 id
+    /// * `to_version: i32` - 新版本的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let diff = docs.diff(1024, 2048, 4096).await?;
+    ///
+    ///     println!("{diff}");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "diff")]
+    pub async fn diff(
+        &self,
+        id: i32,
+        from_version: i32,
+        to_version: i32,
+    ) -> Result<String, YuqueError> {
+        let (from, to) = futures::try_join!(
+            self.get_version(id, from_version),
+            self.get_version(id, to_version)
+        )?;
+
+        let diff = similar::TextDiff::from_lines(from.data.body.as_ref(), to.data.body.as_ref());
+
+        Ok(diff
+            .unified_diff()
+            .context_radius(3)
+            .header(
+                &format!("version {from_version}"),
+                &format!("version {to_version}"),
+            )
+            .to_string())
+    }
+
+    /// Restore a document to a previous version
+    /// 将文档回滚到某个历史版本
+    ///
+    /// 语雀没有单独的回滚接口，这里先通过 [`DocsClient::get_version`] 获取历史版本的内容，
+    /// 再用它更新文档，所以回滚本身也会产生一个新的版本。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    /// * `version_id: i32` - 历史版本的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.restore_version("your namespace", 1024, 2048).await?;
+    ///
+    ///     println!("{:?}", doc);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn restore_version(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        version_id: i32,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let version = self.get_version(id, version_id).await?.data;
+
+        let doc = Doc {
+            title: version.title.into_owned(),
+            slug: version.slug.into_owned(),
+            format: version.format,
+            body: version.body.into_owned(),
+            ..Default::default()
+        };
+
+        self.update_with_repo(namespace, id, doc).await
+    }
+
+    /// Move a document to another repo
+    /// 将文档移动到另一个仓库
+    ///
+    /// 对目标仓库没有写权限时，语雀会返回 403，对应 `YuqueError::NoPermission`；
+    /// 文档或目标仓库不存在时返回 404，对应 `YuqueError::NotFound`。
+    ///
+    /// # Arguments
+    /// * `from_namespace: impl Into<RepoRef>` - 文档当前所在仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    /// * `to_namespace: impl Into<RepoRef>` - 目标仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let doc = docs.move_doc("your namespace", 1024, "another namespace").await?;
+    ///
+    ///     println!("{:?}", doc.data.book_id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn move_doc(
+        &self,
+        from_namespace: impl Into<RepoRef>,
+        id: i32,
+        to_namespace: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let from_namespace: RepoRef = from_namespace.into();
+        let url = format!("{}/move", from_namespace.doc_path(&DocRef::Id(id)));
+
+        let data = serde_json::to_string(&MoveTarget::from(to_namespace.into())).ok();
+
+        let request = self.client.put(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// List the trashed documents of a repository
+    /// 获取仓库回收站中的文档
+    ///
+    /// `delete_with_repo` 删除的文档会先进入回收站，可以通过 [`DocsClient::restore`] 恢复。
+    /// 返回的文档都带有 `deleted_at`。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     for doc in docs.list_trashed("your namespace").await? {
+    ///         println!("{} {:?}", doc.title, doc.deleted_at);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_trashed(
+        &self,
+        namespace: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<Vec<DocListItem<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.trash_path();
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Restore a trashed document
+    /// 从回收站恢复文档
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let deleted = docs.delete_with_repo("your namespace", 1024).await?;
+    ///     let restored = docs.restore("your namespace", deleted.data.id).await?;
+    ///
+    ///     assert!(restored.data.deleted_at.is_none());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn restore(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+    ) -> Result<YuqueResponse<DocDetail<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = format!("{}/{}/restore", namespace.trash_path(), id);
+
+        let request = self.client.put(&url, None)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Like a document
+    /// 点赞文档
+    ///
+    /// 重复点赞不会报错。没有阅读权限时语雀返回 403，对应 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     docs.like("your namespace", 1024).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn like(&self, namespace: impl Into<RepoRef>, id: i32) -> Result<(), YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.likes_path(&DocRef::Id(id));
+
+        let request = self.client.post(&url, None)?;
+
+        let response = self.client.execute(request).await?;
+
+        ensure_success(response, url).await?;
+
+        Ok(())
+    }
+
+    /// Unlike a document
+    /// 取消点赞文档
+    ///
+    /// 没有点赞过时同样返回 `Ok(())`，权限错误与 [`DocsClient::like`] 相同。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     docs.unlike("your namespace", 1024).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn unlike(&self, namespace: impl Into<RepoRef>, id: i32) -> Result<(), YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.likes_path(&DocRef::Id(id));
+
+        let request = self.client.delete(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        ensure_success(response, url).await?;
+
+        Ok(())
+    }
+
+    /// List the users who liked a document
+    /// 获取点赞了文档的用户
+    ///
+    /// 总数也可以从 `DocListItem::likes_count` 得到。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     for user in docs.list_likes("your namespace", 1024).await? {
+    ///         println!("{}", user.login);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_likes(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+    ) -> Result<YuqueResponse<Vec<User<'_>>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.likes_path(&DocRef::Id(id));
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Get the share link of a document
+    /// 获取文档的分享链接
+    ///
+    /// 空间策略禁止分享时，语雀会返回 403，对应 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let share = docs.get_share("your namespace", 1024).await?;
+    ///
+    ///     println!("{:?}", share);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_share(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+    ) -> Result<YuqueResponse<ShareLink<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.share_path(&DocRef::Id(id));
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Create a share link for a document
+    /// 创建文档的分享链接
+    ///
+    /// 空间策略禁止分享时，语雀会返回 403，对应 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `namespace: impl Into<RepoRef>` - 仓库的命名空间/id
+    /// * `id: i32` - 文档的 id
+    /// * `options: ShareOptions` - 访问密码、过期时间
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{ShareOptions, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("your token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let options = ShareOptions::builder()
+    ///                         .password("123456".into())
+    ///                         .build()?;
+    ///
+    ///     let docs = yuque.docs();
+    ///
+    ///     let share = docs.create_share("your namespace", 1024, options).await?;
+    ///
+    ///     println!("{}", share.data.url);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_share(
+        &self,
+        namespace: impl Into<RepoRef>,
+        id: i32,
+        options: ShareOptions,
+    ) -> Result<YuqueResponse<ShareLink<'_>>, YuqueError> {
+        let namespace: RepoRef = namespace.into();
+        let url = namespace.share_path(&DocRef::Id(id));
+
+        let data = serde_json::to_string(&options).ok();
+
+        let request = self.client.post(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{error::Error, ops::Not, time::Duration};
+
+    use futures::StreamExt;
+    use serde_json::json;
+
+    use super::MoveTarget;
+    use crate::{
+        Doc, DocDetail, DocDetailQuery, DocListItem, DocListQuery, DocProperty, DocStatus,
+        QueryParams, RepoRef, ShareLink, ShareOptions, UpdateDoc, Visibility, Yuque, YuqueError,
+        YuqueFormat,
+    };
+
+    macro_rules! aw {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    const TEST_NS: &str = "lzzzt/sdk-test";
+    const TEST_HOST: &str = "https://lzzzt.yuque.com/api/v2";
+
+    pub(crate) fn doc_detail_fixture() -> serde_json::Value {
+        json!({
+            "id": 1024,
+            "slug": "create-by-sdk",
+            "title": "Create By SDK",
+            "book_id": 2048,
+            "book": null,
+            "user_id": 4096,
+            "user": null,
+            "format": "markdown",
+            "body": "This sentence is created by yuque-rust sdk.",
+            "body_draft": "",
+            "body_html": null,
+            "body_lake": null,
+            "creator_id": 4096,
+            "public": 1,
+            "status": 1,
+            "likes_count": 0,
+            "comments_count": 0,
+            "content_updated_at": "2023-03-01T08:00:00.000Z",
+            "deleted_at": null,
+            "created_at": "2023-03-01T08:00:00.000Z",
+            "updated_at": "2023-03-01T08:00:00.000Z"
+        })
+    }
+
+    fn doc_list_item_fixture() -> serde_json::Value {
+        json!({
+            "id": 1024,
+            "slug": "create-by-sdk",
+            "title": "Create By SDK",
+            "description": null,
+            "user_id": 4096,
+            "format": "markdown",
+            "public": 1,
+            "status": 1,
+            "likes_count": 0,
+            "comments_count": 0,
+            "content_updated_at": "2023-03-01T08:00:00.000Z",
+            "book": null,
+            "user": null,
+            "last_editor": {
+                "id": 4096,
+                "type": "User",
+                "login": "lzzzt",
+                "name": "Lzzzt",
+                "avatar_url": "https://cdn.nlark.com/yuque/avatar.png",
+                "created_at": "2023-03-01T08:00:00.000Z",
+                "updated_at": "2023-03-01T08:00:00.000Z"
+            },
+            "created_at": "2023-03-01T08:00:00.000Z",
+            "updated_at": "2023-03-01T08:00:00.000Z"
+        })
+    }
+
+    fn doc_detail_with_body(body: &str) -> DocDetail<'static> {
+        let mut fixture = doc_detail_fixture();
+        fixture["body"] = body.into();
+
+        serde_json::from_value(fixture).unwrap()
+    }
+
+    #[test]
+    fn should_reject_zero_page_limit() {
+        let yuque = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:1".into())
+            .build()
+            .unwrap();
+
+        let docs = yuque.docs();
+
+        assert!(matches!(
+            aw!(docs.list_with_repo_paged("lzzzt/sdk-test", 0, 0)),
+            Err(YuqueError::InvalidParams { .. })
+        ));
+    }
+
+    #[test]
+    fn should_surface_stream_errors_then_end() {
+        let yuque = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:1".into())
+            .build()
+            .unwrap();
+
+        let docs = yuque.docs();
+
+        let items: Vec<_> = aw!(docs.stream_all_docs("lzzzt/sdk-test").collect());
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(YuqueError::Request(_))));
+    }
+
+    #[test]
+    fn should_default_omitted_counts() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_list_item_fixture();
+        let fields = fixture.as_object_mut().unwrap();
+        fields.remove("likes_count");
+        fields.remove("comments_count");
+
+        let item: DocListItem = serde_json::from_value(fixture)?;
+
+        assert_eq!(item.likes_count, 0);
+        assert_eq!(item.comments_count, 0);
+
+        let mut fixture = doc_detail_fixture();
+        let fields = fixture.as_object_mut().unwrap();
+        fields.remove("likes_count");
+        fields.remove("comments_count");
+
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+
+        assert_eq!((detail.likes_count, detail.comments_count), (0, 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_list_item_without_content_update() -> Result<(), Box<dyn Error>> {
+        let item: DocListItem = serde_json::from_value(doc_list_item_fixture())?;
+        assert!(item.content_updated_at.is_some());
+
+        let mut fixture = doc_list_item_fixture();
+        fixture["content_updated_at"] = json!(null);
+
+        let item: DocListItem = serde_json::from_value(fixture)?;
+        assert!(item.content_updated_at.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_compare_content_ignoring_volatile_fields() {
+        let detail = doc_detail_with_body("# Title\r\n\nbody\r\n");
+
+        let mut doc = Doc {
+            title: "Create By SDK".into(),
+            slug: "create-by-sdk".into(),
+            format: YuqueFormat::Markdown,
+            body: "# Title\n\nbody".into(),
+            ..Default::default()
+        };
+
+        assert!(detail.content_equals(&doc));
+
+        doc.body.push_str(" changed");
+        assert!(!detail.content_equals(&doc));
+
+        doc.body = "# Title\n\nbody".into();
+        doc.format = YuqueFormat::Html;
+        assert!(!detail.content_equals(&doc));
+    }
+
+    #[test]
+    fn should_list_docs() -> Result<(), Box<dyn Error>> {
+        dotenv::from_path(".env.dev").ok();
+
+        let token = std::env::var("TOKEN")?;
+
+        let client = Yuque::builder()
+            .token(token)
+            .host(TEST_HOST.into())
+            .build()?
+            .docs();
+
+        let docs = aw!(client.list_with_repo(TEST_NS))?;
+
+        assert!(docs.data.is_empty().not());
+
+        let count = docs
+            .into_iter()
+            .map(|doc| doc.title)
+            .filter(|title| title.to_ascii_lowercase().contains("test"))
+            .count();
+
+        assert!(count > 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_get_doc_detail() -> Result<(), Box<dyn Error>> {
+        dotenv::from_path(".env.dev").ok();
+
+        let token = std::env::var("TOKEN")?;
+
+        let client = Yuque::builder()
+            .token(token)
+            .host(TEST_HOST.into())
+            .build()?
+            .docs();
+
+        let doc =
+            aw!(client.get_with_repo_ns(TEST_NS, "create-by-sdk", Some(&[("raw", "1")])))?.data;
+
+        assert!(doc
+            .body
+            .contains("This sentence is created by yuque-rust sdk."));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_create_then_delete() -> Result<(), Box<dyn Error>> {
+        dotenv::from_path(".env.dev").ok();
+
+        let token = std::env::var("TOKEN")?;
+
+        let client = Yuque::builder()
+            .token(token)
+            .host(TEST_HOST.into())
+            .build()?
+            .docs();
+
+        let doc = Doc::builder()
+            .title("Create By SDK".into())
+            .body("Should be delete!".into())
+            .slug("by-sdk".into())
+            .build()?;
+
+        let created_doc = aw!(client.create_with_repo(TEST_NS, doc.clone()))?.data;
+
+        assert_eq!(doc.title, created_doc.title);
+        assert!(created_doc.body.contains(doc.body.as_str()));
+        assert_eq!(doc.slug, created_doc.slug);
+
+        let deleted_doc = aw!(client.delete_with_repo(TEST_NS, created_doc.id))?.data;
+
+        assert_eq!(doc.title, deleted_doc.title);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_update() -> Result<(), Box<dyn Error>> {
+        dotenv::from_path(".env.dev").ok();
+
+        let token = std::env::var("TOKEN")?;
+
+        let client = Yuque::builder()
+            .token(token)
+            .host(TEST_HOST.into())
+            .build()?
+            .docs();
+
+        let (mut doc, id): (Doc, i32) =
+            aw!(client.get_with_repo_ns(TEST_NS, "create-by-sdk", Some(&[("raw", "1")])))?
+                .data
+                .try_into()?;
+
+        let new_body = doc.body + &format!("\nLast Update: {}.", chrono::Local::now().to_rfc2822());
+
+        doc.body = new_body.clone();
+
+        let updated_doc = aw!(client.update_with_repo(TEST_NS, id, doc))?.data;
+
+        assert_eq!(updated_doc.body, new_body);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_create_then_delete_against_mock_server() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        runtime.block_on(async {
+            let server = MockServer::start().await;
+            let detail = json!({ "data": doc_detail_fixture() });
+
+            Mock::given(method("POST"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs"))
+                .and(header("X-Auth-Token", "token"))
+                .and(body_json(json!({
+                    "title": "Create By SDK",
+                    "slug": "create-by-sdk",
+                    "format": "markdown",
+                    "body": "This sentence is created by yuque-rust sdk.",
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&detail))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            Mock::given(method("DELETE"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs/1024"))
+                .and(header("X-Auth-Token", "token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&detail))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = Yuque::builder()
+                .token("token".into())
+                .host(server.uri())
+                .build()?
+                .docs();
+
+            let doc = Doc::builder()
+                .title("Create By SDK".into())
+                .body("This sentence is created by yuque-rust sdk.".into())
+                .slug("create-by-sdk".into())
+                .build()?;
+
+            let created_doc = client.create_with_repo(TEST_NS, doc.clone()).await?.data;
+
+            assert_eq!(doc.title, created_doc.title);
+            assert_eq!(doc.slug, created_doc.slug);
+
+            let deleted_doc = client.delete_with_repo(TEST_NS, created_doc.id).await?.data;
+
+            assert_eq!(doc.title, deleted_doc.title);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn should_update_against_mock_server() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        runtime.block_on(async {
+            let server = MockServer::start().await;
+
+            Mock::given(method("PUT"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs/1024"))
+                .and(header("X-Auth-Token", "token"))
+                .and(body_json(
+                    json!({ "body": "This sentence is created by yuque-rust sdk." }),
+                ))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({ "data": doc_detail_fixture() })),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = Yuque::builder()
+                .token("token".into())
+                .host(server.uri())
+                .build()?
+                .docs();
+
+            let update = UpdateDoc::builder()
+                .body("This sentence is created by yuque-rust sdk.")
+                .build()?;
+
+            let updated_doc = client.update_with_repo(TEST_NS, 1024, update).await?.data;
+
+            assert_eq!(
+                updated_doc.body,
+                "This sentence is created by yuque-rust sdk."
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn should_collect_markdown_attachment_urls() {
+        let doc = doc_detail_with_body(
+            "# Title\n\
+             ![image.png](https://cdn.nlark.com/yuque/0/2023/png/1/a.png#averageHue=%23f00&id=x)\n\
+             [report.pdf](https://www.yuque.com/attachments/yuque/0/2023/pdf/1/b.pdf)\n\
+             [rust](https://www.rust-lang.org)\n\
+             ![again](https://cdn.nlark.com/yuque/0/2023/png/1/a.png \"same image\")",
+        );
+
+        assert_eq!(
+            doc.attachment_urls(),
+            vec![
+                "https://cdn.nlark.com/yuque/0/2023/png/1/a.png",
+                "https://www.yuque.com/attachments/yuque/0/2023/pdf/1/b.pdf",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_resolve_relative_attachment_urls() {
+        let doc = doc_detail_with_body(
+            "[file](/attachments/yuque/0/2023/zip/1/c.zip) \
+             ![cdn](//cdn.nlark.com/yuque/d.jpg) \
+             [other doc](/lzzzt/sdk-test/create-by-sdk)",
+        );
+
+        assert_eq!(
+            doc.attachment_urls(),
+            vec![
+                "https://www.yuque.com/attachments/yuque/0/2023/zip/1/c.zip",
+                "https://cdn.nlark.com/yuque/d.jpg",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_attachments_of_lookalike_hosts() {
+        let doc = doc_detail_with_body(
+            "[fake](https://evilyuque.com/attachments/yuque/0/2023/zip/1/c.zip)              [fake](https://www.evilyuque.com/attachments/yuque/0/2023/zip/1/d.zip)              [real](https://yuque.com/attachments/yuque/0/2023/zip/1/e.zip)",
+        );
+
+        assert_eq!(
+            doc.attachment_urls(),
+            vec!["https://yuque.com/attachments/yuque/0/2023/zip/1/e.zip"]
+        );
+    }
+
+    #[test]
+    fn should_collect_inline_html_images() {
+        let doc = doc_detail_with_body(
+            "<p><IMG alt=\"x\" src=\"https://cdn.nlark.com/yuque/e.png\" width=\"100\"></p>\n\
+             ![first](https://cdn.yuque.com/yuque/f.png)\n\
+             <img src='https://cdn.nlark.com/yuque/g.png'/>\n\
+             <img src=https://cdn.nlark.com/yuque/h.png />\n\
+             <img src=\"https://example.com/i.png\">",
+        );
+
+        assert_eq!(
+            doc.attachment_urls(),
+            vec![
+                "https://cdn.nlark.com/yuque/e.png",
+                "https://cdn.yuque.com/yuque/f.png",
+                "https://cdn.nlark.com/yuque/g.png",
+                "https://cdn.nlark.com/yuque/h.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_export_markdown_without_overwriting() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/repos/lzzzt/sdk-test/docs/create-by-sdk",
+            200,
+            json!({ "data": doc_detail_fixture() }),
+        );
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?;
+        let docs = client.docs();
+
+        let dir = std::env::temp_dir().join(format!("yuque-rust-export-{}", std::process::id()));
+        let path = dir.join("nested").join("create-by-sdk.md");
+
+        runtime.block_on(docs.export_markdown(TEST_NS, "create-by-sdk", &path, false))?;
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "This sentence is created by yuque-rust sdk."
+        );
+
+        std::fs::write(&path, "local changes")?;
+
+        let result = runtime.block_on(docs.export_markdown(TEST_NS, "create-by-sdk", &path, false));
+        assert!(matches!(
+            result,
+            Err(YuqueError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists
+        ));
+        assert_eq!(std::fs::read_to_string(&path)?, "local changes");
+
+        runtime.block_on(docs.export_markdown(TEST_NS, "create-by-sdk", &path, true))?;
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "This sentence is created by yuque-rust sdk."
+        );
+        // 文件已存在时不会发出请求
+        assert_eq!(mock.requests().len(), 2);
+
+        std::fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_export_repo_and_report_failures() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mut broken = doc_list_item_fixture();
+        broken["slug"] = json!("broken");
+
+        let mut escaping = doc_list_item_fixture();
+        escaping["slug"] = json!("../escape");
+
+        let page = json!({ "data": [doc_list_item_fixture(), broken, escaping] });
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        let server = runtime.block_on(async {
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs"))
+                .and(query_param("offset", "0"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(page))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": [] })))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs/create-by-sdk"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({ "data": doc_detail_fixture() })),
+                )
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/repos/lzzzt/sdk-test/docs/broken"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {} })))
+                .mount(&server)
+                .await;
+
+            server
+        });
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host(server.uri())
+            .build()?;
+        let docs = client.docs();
+
+        assert!(matches!(
+            runtime.block_on(docs.export_repo(TEST_NS, "unused", 0)),
+            Err(YuqueError::InvalidParams { .. })
+        ));
+
+        let dir =
+            std::env::temp_dir().join(format!("yuque-rust-export-repo-{}", std::process::id()));
+
+        let report = runtime.block_on(docs.export_repo(TEST_NS, &dir, 2))?;
+
+        assert_eq!(report.exported, vec!["create-by-sdk".to_string()]);
+        assert_eq!(report.failed.len(), 2);
+        assert!(report
+            .failed
+            .iter()
+            .any(|(slug, e)| slug == "broken" && matches!(e, YuqueError::Deserialize { .. })));
+        assert!(report
+            .failed
+            .iter()
+            .any(|(slug, e)| slug == "../escape" && matches!(e, YuqueError::InvalidParams { .. })));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("create-by-sdk.md"))?,
+            "This sentence is created by yuque-rust sdk."
+        );
+        assert!(dir.join("broken.md").exists().not());
+        assert!(dir.join("../escape.md").exists().not());
+
+        std::fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_recent_docs() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/users/lzzzt/recent-updated",
+            200,
+            json!({ "data": [doc_list_item_fixture()] }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let docs = client.docs();
+
+        let recent = aw!(docs.list_recent("lzzzt", None))?;
+
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].slug, "create-by-sdk");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_and_restore_trashed_docs() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let mut trashed = doc_list_item_fixture();
+        trashed["deleted_at"] = json!("2023-03-02T08:00:00.000Z");
+
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/repos/lzzzt/sdk-test/trash",
+                200,
+                json!({ "data": [trashed] }),
+            )
+            .route(
+                Method::PUT,
+                "/repos/lzzzt/sdk-test/trash/1024/restore",
+                200,
+                json!({ "data": doc_detail_fixture() }),
+            );
 
-    #[test]
-    fn should_create_then_delete() -> Result<(), Box<dyn Error>> {
-        dotenv::from_path(".env.dev").ok();
-
-        let token = std::env::var("TOKEN")?;
-
         let client = Yuque::builder()
-            .token(token)
-            .host(TEST_HOST.into())
-            .build()?
-            .docs();
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?;
+        let docs = client.docs();
+
+        let list = aw!(docs.list_trashed(TEST_NS))?;
+        assert_eq!(list.len(), 1);
+        assert!(list[0].deleted_at.is_some());
+
+        let restored = aw!(docs.restore(TEST_NS, list[0].id))?;
+        assert!(restored.data.deleted_at.is_none());
+
+        assert_eq!(
+            mock.requests(),
+            vec![
+                (
+                    Method::GET,
+                    "/api/v2/repos/lzzzt/sdk-test/trash".to_string()
+                ),
+                (
+                    Method::PUT,
+                    "/api/v2/repos/lzzzt/sdk-test/trash/1024/restore".to_string()
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_and_get_versions() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let version = json!({
+            "id": 2048,
+            "doc_id": 1024,
+            "slug": "create-by-sdk",
+            "title": "Create By SDK",
+            "user_id": 4096,
+            "created_at": "2023-03-01T08:00:00.000Z",
+            "updated_at": "2023-03-01T08:00:00.000Z"
+        });
+
+        let mut detail = version.clone();
+        detail["format"] = json!("markdown");
+        detail["body"] = json!("old body");
+
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/doc_versions",
+                200,
+                json!({ "data": [version] }),
+            )
+            .route(
+                Method::GET,
+                "/doc_versions/2048",
+                200,
+                json!({ "data": detail }),
+            );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?;
+        let docs = client.docs();
+
+        let versions = aw!(docs.list_versions(1024))?;
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].id, 2048);
+
+        let version = aw!(docs.get_version(1024, 2048))?;
+        assert_eq!(version.data.body, "old body");
+
+        assert!(matches!(
+            aw!(docs.get_version(1, 2048)),
+            Err(YuqueError::InvalidParams { .. })
+        ));
+
+        assert_eq!(
+            mock.requests()[0],
+            (Method::GET, "/api/v2/doc_versions?doc_id=1024".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_like_unlike_and_list_likes() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let path = "/api/v2/repos/lzzzt/sdk-test/docs/1024/likes";
+        let user = doc_list_item_fixture()["last_editor"].clone();
+
+        let mock = MockBackend::new()
+            .route(Method::POST, path, 200, json!({ "data": {} }))
+            .route(Method::DELETE, path, 403, json!({ "message": "forbidden" }))
+            .route(Method::GET, path, 200, json!({ "data": [user] }));
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?;
+        let docs = client.docs();
+
+        aw!(docs.like(TEST_NS, 1024))?;
+
+        assert!(matches!(
+            aw!(docs.unlike(TEST_NS, 1024)),
+            Err(YuqueError::NoPermission { .. })
+        ));
+
+        let likes = aw!(docs.list_likes(TEST_NS, 1024))?;
+        assert_eq!(likes.len(), 1);
+        assert_eq!(likes[0].login, "lzzzt");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_upsert_by_slug() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
 
+        use crate::MockBackend;
+
+        let detail = json!({ "data": doc_detail_fixture() });
         let doc = Doc::builder()
             .title("Create By SDK".into())
-            .body("Should be delete!".into())
-            .slug("by-sdk".into())
+            .slug("create-by-sdk".into())
             .build()?;
 
-        let created_doc = aw!(client.create_with_repo(TEST_NS, doc.clone()))?.data;
+        let existing = MockBackend::new()
+            .route(Method::GET, "/docs/create-by-sdk", 200, &detail)
+            .route(Method::PUT, "/docs/1024", 200, &detail);
 
-        assert_eq!(doc.title, created_doc.title);
-        assert!(created_doc.body.contains(doc.body.as_str()));
-        assert_eq!(doc.slug, created_doc.slug);
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(existing.clone())
+            .build()?;
+        let docs = client.docs();
+
+        let upserted = aw!(docs.upsert(TEST_NS, doc.clone()))?;
+        assert!(upserted.created.not());
+        assert_eq!(
+            existing.requests().last(),
+            Some(&(
+                Method::PUT,
+                "/api/v2/repos/lzzzt/sdk-test/docs/1024".to_string()
+            ))
+        );
+
+        let missing = MockBackend::new()
+            .route(Method::GET, "/docs/create-by-sdk", 404, json!({}))
+            .route(Method::POST, "/repos/lzzzt/sdk-test/docs", 200, &detail);
 
-        let deleted_doc = aw!(client.delete_with_repo(TEST_NS, created_doc.id))?.data;
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(missing)
+            .build()?;
+        let docs = client.docs();
 
-        assert_eq!(doc.title, deleted_doc.title);
+        let upserted = aw!(docs.upsert(TEST_NS, doc))?;
+        assert!(upserted.created);
+        assert_eq!(upserted.doc.slug, "create-by-sdk");
 
         Ok(())
     }
 
     #[test]
-    fn should_update() -> Result<(), Box<dyn Error>> {
-        dotenv::from_path(".env.dev").ok();
+    fn should_get_raw_response() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/docs/1024",
+                200,
+                json!({ "data": doc_detail_fixture() }),
+            )
+            .route(
+                Method::GET,
+                "/docs/403",
+                403,
+                json!({ "message": "forbidden" }),
+            );
 
-        let token = std::env::var("TOKEN")?;
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let docs = client.docs();
+
+        let response = aw!(docs.get_with_repo_ns_raw(TEST_NS, 1024, None))?;
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = aw!(response.json())?;
+        assert_eq!(body["data"]["id"], 1024);
+
+        let forbidden = aw!(docs.get_with_repo_ns_raw(TEST_NS, 403, None));
+        assert!(matches!(forbidden, Err(YuqueError::NoPermission { .. })));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn should_diff_versions() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
+
+        use crate::MockBackend;
+
+        let version = |id: i32, body: &str| {
+            json!({ "data": {
+                "id": id,
+                "doc_id": 1024,
+                "slug": "create-by-sdk",
+                "title": "Create By SDK",
+                "format": "markdown",
+                "body": body,
+                "created_at": "2023-03-01T08:00:00.000Z",
+                "updated_at": "2023-03-01T08:00:00.000Z"
+            } })
+        };
+
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/doc_versions/1",
+                200,
+                version(1, "# Title\nold line\nsame\n"),
+            )
+            .route(
+                Method::GET,
+                "/doc_versions/2",
+                200,
+                version(2, "# Title\nnew line\nsame\n"),
+            );
 
         let client = Yuque::builder()
-            .token(token)
-            .host(TEST_HOST.into())
-            .build()?
-            .docs();
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let docs = client.docs();
+
+        assert_eq!(
+            aw!(docs.diff(1024, 1, 2))?,
+            concat!(
+                "--- version 1\n",
+                "+++ version 2\n",
+                "@@ -1,3 +1,3 @@\n",
+                " # Title\n",
+                "-old line\n",
+                "+new line\n",
+                " same\n",
+            )
+        );
+        assert_eq!(aw!(docs.diff(1024, 1, 1))?, "");
+
+        assert!(matches!(
+            aw!(docs.diff(1024, 1, 3)),
+            Err(YuqueError::NotFound { .. })
+        ));
+        assert!(matches!(
+            aw!(docs.diff(2048, 1, 2)),
+            Err(YuqueError::InvalidParams { .. })
+        ));
 
-        let (mut doc, id): (Doc, i32) =
-            aw!(client.get_with_repo_ns(TEST_NS, "create-by-sdk", Some(&[("raw", "1")])))?
-                .data
-                .try_into()?;
+        Ok(())
+    }
 
-        let new_body = doc.body + &format!("\nLast Update: {}.", chrono::Local::now().to_rfc2822());
+    #[test]
+    fn should_get_many_in_order() -> Result<(), Box<dyn Error>> {
+        use reqwest::Method;
 
-        doc.body = new_body.clone();
+        use crate::MockBackend;
 
-        let updated_doc = aw!(client.update_with_repo(TEST_NS, id, doc))?.data;
+        let mut second = doc_detail_fixture();
+        second["id"] = json!(2048);
+        second["slug"] = json!("second");
 
-        assert_eq!(updated_doc.body, new_body);
+        let mock = MockBackend::new()
+            .route(
+                Method::GET,
+                "/docs/first",
+                200,
+                json!({ "data": doc_detail_fixture() }),
+            )
+            .route(Method::GET, "/docs/second", 200, json!({ "data": second }));
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let docs = client.docs();
+
+        let fetched = aw!(docs.get_many(TEST_NS, &["second", "missing", "first"], 2));
+
+        assert_eq!(fetched.len(), 3);
+        assert_eq!(fetched[0].as_ref().map(|doc| doc.id).ok(), Some(2048));
+        assert!(matches!(fetched[1], Err(YuqueError::NotFound { .. })));
+        assert_eq!(fetched[2].as_ref().map(|doc| doc.id).ok(), Some(1024));
+
+        assert!(aw!(docs.get_many(TEST_NS, &[], 0)).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_skip_not_modified_doc() -> Result<(), Box<dyn Error>> {
+        use reqwest::{header::IF_MODIFIED_SINCE, Method};
+
+        use crate::{MockBackend, Precondition};
+
+        let detail: DocDetail = serde_json::from_value(doc_detail_fixture())?;
+
+        let (name, value) = Precondition::from(&detail).header();
+        assert_eq!(name, IF_MODIFIED_SINCE);
+        assert_eq!(value, "Wed, 01 Mar 2023 08:00:00 GMT");
+
+        let not_modified = MockBackend::new().route(Method::GET, "/docs/1024", 304, json!({}));
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(not_modified)
+            .build()?;
+        let docs = client.docs();
+
+        let cached = aw!(docs.get_if_modified(TEST_NS, 1024, &detail))?;
+        assert!(cached.is_none());
+
+        let changed = MockBackend::new().route(
+            Method::GET,
+            "/docs/1024",
+            200,
+            json!({ "data": doc_detail_fixture() }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(changed)
+            .build()?;
+        let docs = client.docs();
+
+        let fresh =
+            aw!(docs.get_if_modified(TEST_NS, 1024, Precondition::ETag("\"etag\"".into())))?;
+        assert_eq!(fresh.map(|doc| doc.data.id), Some(1024));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_count_words_of_mixed_text() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_detail_fixture();
+        fixture["body"] =
+            json!("# Hello 语雀\n\nRust's `async` 很好用，我们 don't wait.\n\n- 1. 第一项 item-2");
+
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+
+        // Hello, Rust's, async, don't, wait, 1, item, 2 + 语雀很好用我们第一项
+        assert_eq!(detail.word_count(), 8 + 10);
+        assert_eq!(
+            detail.reading_time(),
+            Duration::from_millis(8 * 300 + 10 * 200)
+        );
+
+        let mut fixture = doc_detail_fixture();
+        fixture["body"] = json!("");
+        let empty: DocDetail = serde_json::from_value(fixture)?;
+        assert_eq!(empty.word_count(), 0);
+        assert_eq!(empty.reading_time(), Duration::ZERO);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_render_markdown_body() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_detail_fixture();
+        fixture["body"] = json!(concat!(
+            "# Title\n\n",
+            "```rust\nfn main() {}\n```\n\n",
+            "| a | b |\n| --- | --- |\n| 1 | 2 |\n"
+        ));
+
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+
+        assert_eq!(
+            detail.render_html(),
+            concat!(
+                "<h1>Title</h1>\n",
+                "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n",
+                "<table><thead><tr><th>a</th><th>b</th></tr></thead><tbody>\n",
+                "<tr><td>1</td><td>2</td></tr>\n",
+                "</tbody></table>\n"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_hash_doc_list_items_by_id() -> Result<(), Box<dyn Error>> {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashSet},
+            hash::{Hash, Hasher},
+        };
+
+        let hash = |item: &DocListItem| {
+            let mut hasher = DefaultHasher::new();
+            item.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let item: DocListItem = serde_json::from_value(doc_list_item_fixture())?;
+        let same: DocListItem = serde_json::from_value(doc_list_item_fixture())?;
+
+        let mut edited = doc_list_item_fixture();
+        edited["updated_at"] = json!("2024-01-01T00:00:00.000Z");
+        let edited: DocListItem = serde_json::from_value(edited)?;
+
+        let mut other = doc_list_item_fixture();
+        other["id"] = json!(2048);
+        let other: DocListItem = serde_json::from_value(other)?;
+
+        assert_eq!(hash(&item), hash(&edited));
+        assert_ne!(item, edited);
+
+        let unique: HashSet<_> = [item, same, other].into_iter().collect();
+        assert_eq!(unique.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_display_doc_list_item() -> Result<(), Box<dyn Error>> {
+        let mut item: DocListItem = serde_json::from_value(doc_list_item_fixture())?;
+        let updated_at = item.updated_at.format("%Y-%m-%d %H:%M");
+
+        assert_eq!(
+            item.to_string(),
+            format!("Create By SDK (create-by-sdk) — updated {updated_at}")
+        );
+
+        item.title = " ".into();
+        item.content_updated_at = None;
+        item.updated_at += chrono::Duration::days(1);
+        let updated_at = item.updated_at.format("%Y-%m-%d %H:%M");
+
+        assert_eq!(
+            item.to_string(),
+            format!("untitled (create-by-sdk) — updated {updated_at}")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_doc_status() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_list_item_fixture();
+        fixture["status"] = json!(0);
+
+        let item: DocListItem = serde_json::from_value(fixture)?;
+        assert_eq!(item.status, DocStatus::Draft);
+        assert!(item.status.is_draft() && !item.status.is_published());
+        assert_eq!(serde_json::to_value(&item)?["status"], json!(0));
+
+        let detail: DocDetail = serde_json::from_value(doc_detail_fixture())?;
+        assert!(detail.status.is_published());
+
+        let mut fixture = doc_detail_fixture();
+        fixture["status"] = json!(3);
+        assert!(serde_json::from_value::<DocDetail>(fixture).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_round_trip_doc_detail() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_detail_fixture();
+        fixture["deleted_at"] = json!("2023-03-02T08:00:00.000Z");
+        fixture["tags"] = json!(["rust"]);
+
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+
+        let cached = serde_json::to_string(&detail)?;
+        let restored: DocDetail = serde_json::from_str(&cached)?;
+
+        assert_eq!(serde_json::to_string(&restored)?, cached);
+        assert_eq!(restored.created_at, detail.created_at);
+        assert_eq!(restored.deleted_at, detail.deleted_at);
+        assert!(restored.public && restored.status.is_published());
+        assert_eq!(restored.tags, Some(vec!["rust".into()]));
+        assert_eq!(restored, detail);
+
+        let mut fresh = doc_detail_fixture();
+        fresh["deleted_at"] = json!("2023-03-02T08:00:00.000Z");
+        fresh["tags"] = json!(["rust"]);
+        fresh["body"] = json!("changed");
+        let fresh: DocDetail = serde_json::from_value(fresh)?;
+
+        assert_ne!(fresh, restored);
+
+        let doc: Doc = restored.clone().try_into()?;
+        assert_eq!(doc.title, restored.title);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_optional_properties() -> Result<(), Box<dyn Error>> {
+        let detail: DocDetail = serde_json::from_value(doc_detail_fixture())?;
+        assert!(detail.hits.is_none() && detail.tags.is_none());
+
+        let mut fixture = doc_detail_fixture();
+        fixture["hits"] = json!(42);
+        fixture["tags"] = json!(["rust", "sdk"]);
+
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+        assert_eq!(detail.hits, Some(42));
+        assert_eq!(detail.tags.unwrap(), ["rust", "sdk"]);
+
+        assert_eq!(
+            QueryParams::from(
+                DocDetailQuery::new()
+                    .with(DocProperty::Tags)
+                    .with(DocProperty::Tags)
+            ),
+            QueryParams::from(&[("optionalProperties", "tags")])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_doc_list_query() -> Result<(), Box<dyn Error>> {
+        assert!(QueryParams::from(DocListQuery::new()).is_empty());
+
+        let query = DocListQuery::new()
+            .limit(500)
+            .with(DocProperty::Hits)
+            .with(DocProperty::Hits);
+
+        assert_eq!(
+            QueryParams::from(query),
+            QueryParams::from(&[("limit", "100"), ("optionalProperties", "hits")])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_move_target() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            serde_json::to_value(MoveTarget::from(RepoRef::from("lzzzt/archive")))?,
+            json!({ "target_namespace": "lzzzt/archive" })
+        );
+        assert_eq!(
+            serde_json::to_value(MoveTarget::from(RepoRef::from(1024)))?,
+            json!({ "target_book_id": 1024 })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_only_changed_fields_of_update() -> Result<(), Box<dyn Error>> {
+        let update = UpdateDoc::builder().title("x").build()?;
+
+        assert_eq!(serde_json::to_value(&update)?, json!({ "title": "x" }));
+
+        let doc = Doc::builder()
+            .title("title".into())
+            .slug("slug".into())
+            .build()?;
+
+        assert_eq!(
+            serde_json::to_value(UpdateDoc::from(doc))?,
+            json!({ "title": "title", "slug": "slug", "format": "markdown", "body": "" })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_public_and_status_only_when_set() -> Result<(), Box<dyn Error>> {
+        let doc = Doc::builder()
+            .title("title".into())
+            .slug("slug".into())
+            .build()?;
+
+        let json = serde_json::to_value(&doc)?;
+        assert!(json.get("public").is_none() && json.get("status").is_none());
+
+        let doc = Doc::builder()
+            .title("title".into())
+            .public(Visibility::Private)
+            .status(DocStatus::Draft)
+            .build()?;
+
+        let json = serde_json::to_value(&doc)?;
+        assert_eq!((&json["public"], &json["status"]), (&json!(0), &json!(0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_borrowed_doc_detail() -> Result<(), Box<dyn Error>> {
+        let detail = doc_detail_with_body("borrowed body");
+
+        let doc = Doc::try_from(&detail)?;
+
+        assert_eq!(doc.title, detail.title);
+        assert_eq!(doc.slug, detail.slug);
+        assert_eq!(doc.body, "borrowed body");
+        assert_eq!(
+            (doc.public, doc.status),
+            (Some(Visibility::Public), Some(DocStatus::Normal))
+        );
+        assert_eq!(detail.id, 1024);
+
+        let mut fixture = doc_detail_fixture();
+        fixture["format"] = "lake".into();
+        let detail: DocDetail = serde_json::from_value(fixture.clone())?;
+
+        assert!(Doc::try_from(&detail).is_err());
+
+        fixture["body_lake"] = "<!doctype lake><p>lake body</p>".into();
+        let detail: DocDetail = serde_json::from_value(fixture)?;
+        let doc = Doc::try_from(detail)?;
+
+        assert!(matches!(doc.format, YuqueFormat::Lake));
+        assert_eq!(doc.body, "<!doctype lake><p>lake body</p>");
+        assert!(doc.ensure_markdown().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_doc_without_last_editor() -> Result<(), Box<dyn Error>> {
+        let doc: DocListItem = serde_json::from_value(doc_list_item_fixture())?;
+
+        assert_eq!(doc.last_editor.map(|user| user.login), Some("lzzzt".into()));
+
+        let mut fixture = doc_list_item_fixture();
+        fixture.as_object_mut().unwrap().remove("last_editor");
+
+        let doc: DocListItem = serde_json::from_value(fixture)?;
+
+        assert!(doc.last_editor.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_only_set_share_options() -> Result<(), Box<dyn Error>> {
+        let options = ShareOptions::builder().password("123456".into()).build()?;
+
+        assert_eq!(
+            serde_json::to_value(options)?,
+            json!({ "password": "123456" })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_deserialize_share_link() -> Result<(), Box<dyn Error>> {
+        let share: ShareLink = serde_json::from_value(json!({
+            "url": "https://www.yuque.com/lzzzt/sdk-test/create-by-sdk?share=abc",
+            "password": null,
+            "expires_at": "2023-03-01T08:00:00.000Z"
+        }))?;
+
+        assert!(share.password.is_none());
+        assert!(share.expires_at.is_some());
+
+        let share: ShareLink = serde_json::from_value(json!({ "url": "https://www.yuque.com/s" }))?;
+
+        assert!(share.expires_at.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_map_format_to_body_field() -> Result<(), Box<dyn Error>> {
+        let mut fixture = doc_detail_fixture();
+        fixture["body_html"] = "<p>html</p>".into();
+        let doc: DocDetail = serde_json::from_value(fixture)?;
+
+        assert_eq!(doc.body_as(YuqueFormat::Markdown)?, doc.body);
+        assert_eq!(doc.body_as(YuqueFormat::Html)?, "<p>html</p>");
+        assert!(doc.body_as(YuqueFormat::Lake).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_doc_with_null_relations() -> Result<(), Box<dyn Error>> {
+        let repo = crate::repos::test::repo_detail_fixture();
+
+        for field in ["book", "user", "last_editor"] {
+            let mut fixture = doc_list_item_fixture();
+            fixture["book"] = repo.clone();
+            fixture["user"] = repo["user"].clone();
+            fixture[field] = serde_json::Value::Null;
+
+            let doc: DocListItem = serde_json::from_value(fixture)?;
+
+            assert_eq!(doc.book.is_none(), field == "book");
+            assert_eq!(doc.user.is_none(), field == "user");
+            assert_eq!(doc.last_editor.is_none(), field == "last_editor");
+        }
+
+        let mut fixture = doc_list_item_fixture();
+        fixture["book"] = repo;
+        fixture["book"]["user"] = serde_json::Value::Null;
+
+        let doc: DocListItem = serde_json::from_value(fixture)?;
+
+        assert!(doc.book.unwrap().user.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_empty_title_before_request() -> Result<(), Box<dyn Error>> {
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("http://127.0.0.1:9".into())
+            .build()?
+            .docs();
+
+        let doc = Doc::builder().title(" \n".into()).build()?;
+
+        let result = aw!(client.create_with_repo(TEST_NS, doc));
+
+        assert!(matches!(result, Err(YuqueError::InvalidParams { .. })));
 
         Ok(())
     }
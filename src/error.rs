@@ -1,24 +1,118 @@
+use std::time::Duration;
+
+use derive_builder::UninitializedFieldError;
 use reqwest::header::InvalidHeaderValue;
 use thiserror::Error;
 
+/// SDK 返回的错误
+///
+/// 以后可能会增加新的错误（例如限流），`match` 时需要保留通配分支 `_ => ...`。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::YuqueError;
+///
+/// fn retryable(e: &YuqueError) -> bool {
+///     match e {
+///         YuqueError::RateLimited { .. } => true,
+///         _ => false,
+///     }
+/// }
+/// ```
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum YuqueError {
     #[error("Internal Error: {0}.")]
     Internal(String),
     #[error("Request Error: {0}.")]
     Request(#[from] reqwest::Error),
-    #[error("Invalid Params: {0}. The requested parameters are incorrect, or the necessary information is missing, please compare the documentation.")]
-    InvalidParams(String),
-    #[error("Invalid User Info: {0}. Incorrect user information for the interface requiring user authentication.")]
-    InvalidUserInfo(String),
-    #[error("No Permission: {0}. Missing permission for the corresponding function.")]
-    NoPermission(String),
-    #[error("Not Found: Data does not exist, or is not open.")]
-    NotFound(String),
-    #[error("Server Exception: {0}")]
-    ServerException(String),
+    #[error("Invalid Params: {url}{}. The requested parameters are incorrect, or the necessary information is missing, please compare the documentation.", detail(.message, .code))]
+    InvalidParams {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    #[error("Invalid User Info: {url}{}. Incorrect user information for the interface requiring user authentication.", detail(.message, .code))]
+    InvalidUserInfo {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    /// 由 `Yuque::verify` 返回，token 无效或已过期
+    #[error("Invalid Token: {url}. The token is invalid or expired, please check it.")]
+    InvalidToken { url: String },
+    #[error("No Permission: {url}{}. Missing permission for the corresponding function.", detail(.message, .code))]
+    NoPermission {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    #[error("Not Found: {url}{}. Data does not exist, or is not open.", detail(.message, .code))]
+    NotFound {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    #[error("Payload Too Large: {url}{}. The request body exceeds the size limit of the server.", detail(.message, .code))]
+    PayloadTooLarge {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    #[error("Rate Limited: {url}. Too many requests, retry after {retry_after:?}.")]
+    RateLimited {
+        url: String,
+        retry_after: Option<Duration>,
+    },
+    #[error("Server Exception: {url}{}", detail(.message, .code))]
+    ServerException {
+        url: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+    /// 条件请求命中了缓存（304），见 `DocsClient::get_if_modified`
+    #[error("Not Modified: {url}. The resource has not changed since the given precondition.")]
+    NotModified { url: String },
     #[error("Not Support Format: {0}.")]
     NotSupportFormat(String),
+    /// 读写本地文件失败，如导出文档时
+    #[error("IO Error: {0}.")]
+    Io(#[from] std::io::Error),
+    #[error("Builder Error: {0}.")]
+    Builder(String),
+    /// 响应体不符合预期的结构，通常是语雀的返回格式有变化
+    #[error("Deserialize Error: {url}. {source}.")]
+    Deserialize {
+        url: String,
+        source: serde_json::Error,
+    },
+}
+
+impl YuqueError {
+    /// 在发出请求前检查到的参数错误
+    pub(crate) fn invalid_params(url: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidParams {
+            url: url.into(),
+            message: Some(message.into()),
+            code: None,
+        }
+    }
+}
+
+/// 语雀返回的错误信息，格式为 ` (code: message)`
+fn detail(message: &Option<String>, code: &Option<String>) -> String {
+    match (code, message) {
+        (Some(code), Some(message)) => format!(" ({code}: {message})"),
+        (None, Some(message)) => format!(" ({message})"),
+        (Some(code), None) => format!(" ({code})"),
+        (None, None) => String::new(),
+    }
+}
+
+impl From<UninitializedFieldError> for YuqueError {
+    fn from(value: UninitializedFieldError) -> Self {
+        Self::Builder(value.to_string())
+    }
 }
 
 impl From<InvalidHeaderValue> for YuqueError {
@@ -32,3 +126,25 @@ impl From<serde_json::Error> for YuqueError {
         Self::Internal(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{error::Error, io};
+
+    use super::YuqueError;
+
+    #[test]
+    fn should_convert_io_errors() {
+        fn read() -> Result<(), YuqueError> {
+            Err(io::Error::new(io::ErrorKind::NotFound, "missing.md"))?
+        }
+
+        let error = read().unwrap_err();
+
+        assert_eq!(error.to_string(), "IO Error: missing.md.");
+        assert!(error
+            .source()
+            .and_then(|source| source.downcast_ref::<io::Error>())
+            .is_some_and(|source| source.kind() == io::ErrorKind::NotFound));
+    }
+}
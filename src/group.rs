@@ -1,7 +1,10 @@
 use chrono::{DateTime, Local};
-use serde::Deserialize;
+use derive_builder::Builder;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{time_serde, User};
+use crate::{
+    ensure_success, handle_response, time_serde, User, UserDetail, Yuque, YuqueError, YuqueResponse,
+};
 
 /// id - GroupUser Id
 /// group_id - 团队编号
@@ -18,9 +21,454 @@ pub struct GroupUser<'a> {
     pub group: User<'a>,
     pub user_id: i32,
     pub user: User<'a>,
-    pub role: i32,
+    pub role: GroupRole,
     #[serde(with = "time_serde")]
     pub created_at: DateTime<Local>,
     #[serde(with = "time_serde")]
     pub updated_at: DateTime<Local>,
 }
+
+/// 团队成员的角色
+///
+/// 与语雀接口使用的数字互相转换：`0` - Owner，`1` - Member，其他数字反序列化时会报错。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRole {
+    Owner,
+    Member,
+}
+
+impl From<GroupRole> for u8 {
+    fn from(value: GroupRole) -> Self {
+        match value {
+            GroupRole::Owner => 0,
+            GroupRole::Member => 1,
+        }
+    }
+}
+
+impl Serialize for GroupRole {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8((*self).into())
+    }
+}
+
+impl<'de> Deserialize<'de> for GroupRole {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value: u8 = Deserialize::deserialize(deserializer)?;
+        match value {
+            0 => Ok(GroupRole::Owner),
+            1 => Ok(GroupRole::Member),
+            _ => Err(D::Error::custom(format!("invalid group role: {value}"))),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MemberRole {
+    role: GroupRole,
+}
+
+/// 用于更新团队资料，只会提交设置过的字段
+///
+/// # Fields
+/// * `name: Option<String>` - 名称
+/// * `description: Option<String>` - 介绍
+/// * `avatar: Option<String>` - 头像 URL，接口不支持直接上传图片，需要先上传到可访问的地址
+#[derive(Builder, Serialize, Clone, Default, Debug)]
+pub struct GroupProfile {
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "avatar_url")]
+    pub avatar: Option<String>,
+}
+
+impl GroupProfile {
+    /// 创建团队资料
+    pub fn builder() -> GroupProfileBuilder {
+        GroupProfileBuilder::default()
+    }
+}
+
+pub struct GroupsClient {
+    pub(crate) client: Yuque,
+}
+
+impl GroupsClient {
+    /// List the groups of a user
+    /// 获取用户加入的团队列表
+    ///
+    /// # Arguments
+    /// * `user` - 用户的 login 或编号
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     let response = groups.list_of_user("username").await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_of_user(
+        &self,
+        user: impl ToString,
+    ) -> Result<YuqueResponse<Vec<User<'_>>>, YuqueError> {
+        let url = format!("/users/{}/groups", user.to_string());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Count the groups of a user
+    /// 获取用户加入的团队总数
+    ///
+    /// 和 [`crate::DocsClient::count_with_repo`] 一样只请求一条数据并读取 `meta.total`，
+    /// 服务端没有返回总数时为 `None`。
+    ///
+    /// # Arguments
+    /// * `user` - 用户的 login 或编号
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let count = yuque.groups().count_of_user("username").await?;
+    ///
+    ///     println!("{:?}", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_of_user(&self, user: impl ToString) -> Result<Option<u32>, YuqueError> {
+        let url = format!("/users/{}/groups", user.to_string());
+
+        let request = self
+            .client
+            .get(&url)?
+            .query(&[("offset", "0"), ("limit", "1")]);
+
+        let response = self.client.execute(request).await?;
+
+        let response: YuqueResponse<Vec<User>> = handle_response(response, url).await?;
+
+        Ok(response.total())
+    }
+
+    /// Get a group
+    /// 获取团队详情
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login 或编号
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     let response = groups.detail("group login").await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn detail(
+        &self,
+        group: impl ToString,
+    ) -> Result<YuqueResponse<UserDetail<'_>>, YuqueError> {
+        let url = format!("/groups/{}", group.to_string());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// update group profile
+    /// 更新团队的名称、介绍和头像
+    ///
+    /// 只有团队的所有者才能修改，其他成员会得到 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login
+    /// * `profile` - 需要更新的团队资料
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{GroupProfile, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     let profile = GroupProfile::builder()
+    ///                     .description("team description".into())
+    ///                     .build()?;
+    ///
+    ///     let response = groups.update_profile("group login", profile).await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_profile(
+        &self,
+        group: impl ToString,
+        profile: GroupProfile,
+    ) -> Result<YuqueResponse<UserDetail<'_>>, YuqueError> {
+        let url = format!("/groups/{}", group.to_string());
+
+        let data = serde_json::to_string(&profile).ok();
+
+        let request = self.client.put(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await.map_err(|e| match e {
+            YuqueError::NoPermission { url, message, code } => YuqueError::NoPermission {
+                url,
+                message: message
+                    .or_else(|| Some("only the group owner can update the profile".into())),
+                code,
+            },
+            e => e,
+        })
+    }
+
+    /// List the members of a group
+    /// 获取团队成员列表
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login 或编号
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     let response = groups.list_members("group login").await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_members(
+        &self,
+        group: impl ToString,
+    ) -> Result<YuqueResponse<Vec<GroupUser<'_>>>, YuqueError> {
+        let url = format!("/groups/{}/users", group.to_string());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Add a member to a group, or change the role of a member
+    /// 添加团队成员或修改成员的角色
+    ///
+    /// 没有管理权限时返回 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login 或编号
+    /// * `login` - 成员的 login
+    /// * `role` - 成员的角色
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{GroupRole, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     let response = groups.add_member("group login", "username", GroupRole::Member).await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn add_member(
+        &self,
+        group: impl ToString,
+        login: impl ToString,
+        role: GroupRole,
+    ) -> Result<YuqueResponse<GroupUser<'_>>, YuqueError> {
+        let url = format!("/groups/{}/users/{}", group.to_string(), login.to_string());
+
+        let data = serde_json::to_string(&MemberRole { role }).ok();
+
+        let request = self.client.put(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Remove a member from a group
+    /// 移除团队成员
+    ///
+    /// 没有管理权限时返回 `YuqueError::NoPermission`。
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login 或编号
+    /// * `login` - 成员的 login
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let groups = yuque.groups();
+    ///
+    ///     groups.remove_member("group login", "username").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn remove_member(
+        &self,
+        group: impl ToString,
+        login: impl ToString,
+    ) -> Result<(), YuqueError> {
+        let url = format!("/groups/{}/users/{}", group.to_string(), login.to_string());
+
+        let request = self.client.delete(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        ensure_success(response, url).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GroupProfile, GroupRole, MemberRole};
+
+    #[test]
+    fn should_serialize_only_set_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let profile = GroupProfile::builder()
+            .description("team description".into())
+            .build()?;
+
+        assert_eq!(
+            serde_json::to_string(&profile)?,
+            r#"{"description":"team description"}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_count_groups_from_meta() -> Result<(), Box<dyn std::error::Error>> {
+        use reqwest::Method;
+
+        use crate::{MockBackend, Yuque};
+
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/users/lzzzt/groups",
+            200,
+            serde_json::json!({ "data": [], "meta": { "total": 3 } }),
+        );
+
+        let groups = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock.clone())
+            .build()?
+            .groups();
+
+        assert_eq!(
+            tokio_test::block_on(groups.count_of_user("lzzzt"))?,
+            Some(3)
+        );
+        assert_eq!(mock.requests().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_role_as_number() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            serde_json::to_string(&MemberRole {
+                role: GroupRole::Owner
+            })?,
+            r#"{"role":0}"#
+        );
+        assert_eq!(serde_json::to_string(&GroupRole::Member)?, "1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_deserialize_role_from_number() {
+        assert_eq!(
+            serde_json::from_str::<GroupRole>("0").unwrap(),
+            GroupRole::Owner
+        );
+        assert_eq!(
+            serde_json::from_str::<GroupRole>("1").unwrap(),
+            GroupRole::Member
+        );
+        assert!(serde_json::from_str::<GroupRole>("2").is_err());
+    }
+}
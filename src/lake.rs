@@ -0,0 +1,159 @@
+use serde::Serialize;
+
+const LAKE_HEADER: &str = r#"<!doctype lake><meta name="doc-version" content="1" /><meta name="viewport" content="adapt" />"#;
+
+/// Lake 格式正文的构建器
+///
+/// 按顺序拼接段落、标题和代码块，生成 `YuqueFormat::Lake` 的 `Doc::body`。
+/// 文本会被转义，代码块以语雀编辑器使用的 `codeblock` 卡片表示。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{Doc, Lake, YuqueFormat};
+///
+/// let body = Lake::new()
+///     .heading(1, "Hello")
+///     .paragraph("created by yuque-rust")
+///     .code_block("rust", "fn main() {}")
+///     .build();
+///
+/// let doc = Doc::builder()
+///     .title("Hello".into())
+///     .format(YuqueFormat::Lake)
+///     .body(body)
+///     .build()
+///     .unwrap();
+///
+/// assert!(doc.body.starts_with("<!doctype lake>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lake {
+    blocks: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CodeBlockCard<'a> {
+    mode: &'a str,
+    code: &'a str,
+}
+
+impl Lake {
+    /// 创建空的正文
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个段落
+    pub fn paragraph(mut self, text: impl AsRef<str>) -> Self {
+        self.blocks
+            .push(format!("<p>{}</p>", escape(text.as_ref())));
+        self
+    }
+
+    /// 追加一个标题，`level` 会被限制在 1 到 6 之间
+    pub fn heading(mut self, level: u8, text: impl AsRef<str>) -> Self {
+        let level = level.clamp(1, 6);
+        self.blocks
+            .push(format!("<h{level}>{}</h{level}>", escape(text.as_ref())));
+        self
+    }
+
+    /// 追加一个代码块
+    ///
+    /// # Arguments
+    /// * `language` - 代码的语言，如 `rust`，为空时按纯文本显示
+    /// * `code` - 代码内容
+    pub fn code_block(mut self, language: impl AsRef<str>, code: impl AsRef<str>) -> Self {
+        let card = CodeBlockCard {
+            mode: language.as_ref(),
+            code: code.as_ref(),
+        };
+        // 只包含字符串字段，序列化不会失败
+        let value = serde_json::to_string(&card).unwrap_or_default();
+
+        self.blocks.push(format!(
+            r#"<card type="block" name="codeblock" value="data:{}"></card>"#,
+            encode_uri_component(&value)
+        ));
+        self
+    }
+
+    /// 生成 Lake 格式的正文
+    pub fn build(&self) -> String {
+        let mut body = String::from(LAKE_HEADER);
+        self.blocks.iter().for_each(|block| body.push_str(block));
+        body
+    }
+}
+
+impl From<Lake> for String {
+    fn from(value: Lake) -> Self {
+        value.build()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// 与 JavaScript 的 `encodeURIComponent` 一致，语雀的卡片数据以这种方式编码
+fn encode_uri_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lake;
+
+    #[test]
+    fn should_build_lake_body() {
+        let body = Lake::new()
+            .heading(9, "A & B")
+            .paragraph("<script>")
+            .code_block("rust", "let a = \"中\";")
+            .build();
+
+        assert_eq!(
+            body,
+            concat!(
+                r#"<!doctype lake><meta name="doc-version" content="1" /><meta name="viewport" content="adapt" />"#,
+                "<h6>A &amp; B</h6>",
+                "<p>&lt;script&gt;</p>",
+                r#"<card type="block" name="codeblock" value="data:%7B%22mode%22%3A%22rust%22%2C%22code%22%3A%22let%20a%20%3D%20%5C%22%E4%B8%AD%5C%22%3B%22%7D"></card>"#,
+            )
+        );
+        assert_eq!(String::from(Lake::new()), Lake::new().build());
+    }
+}
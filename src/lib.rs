@@ -1,28 +1,64 @@
-use std::{borrow::Cow, fmt::Display};
+use std::fmt::Display;
 
-use ::serde::{Deserialize, Serialize};
-use chrono::{DateTime, Local};
-use rand::Rng;
-use reqwest::Method;
+use std::time::Duration;
 
+use ::serde::{de::DeserializeOwned, Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method, Response, StatusCode,
+};
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building yuque-rust for wasm32 requires the `wasm` feature");
+
+mod attachment;
+mod backend;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 mod docs;
 mod error;
 mod group;
+#[cfg(feature = "lake")]
+mod lake;
+mod query;
+mod reference;
 mod repos;
 mod response;
+mod search;
 mod serde;
+mod slug;
+mod statistics;
+#[cfg(feature = "toc")]
+mod toc;
 mod user;
+pub use crate::serde::CamelCase;
 use crate::serde::*;
+pub use attachment::*;
+pub use backend::*;
 pub use client::*;
 pub use docs::*;
 pub use error::*;
 pub use group::*;
+#[cfg(feature = "lake")]
+pub use lake::*;
+pub use query::*;
+pub use reference::*;
 pub use repos::*;
 pub use response::*;
+pub use search::*;
+pub use slug::*;
+pub use statistics::*;
+#[cfg(feature = "toc")]
+pub use toc::*;
 pub use user::*;
 
 pub const DEFAULT_USER_AGENT: &str = "@yuque/sdk";
+/// 默认的 api 路径，`host` 没有路径时会被拼接在后面
+pub const DEFAULT_BASE_PATH: &str = "/api/v2";
+/// 默认的请求超时时间
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug)]
 pub enum RequestMethod {
@@ -56,26 +92,114 @@ impl From<RequestMethod> for Method {
     }
 }
 
-pub(crate) fn judge_status_code(status_code: u16, url: String) -> Result<(), YuqueError> {
-    match status_code {
-        400 => Err(YuqueError::InvalidParams(url)),
-        401 => Err(YuqueError::InvalidUserInfo(url)),
-        403 => Err(YuqueError::NoPermission(url)),
-        404 => Err(YuqueError::NotFound(url)),
-        500 => Err(YuqueError::ServerException(url)),
-        _ => Ok(()),
+/// 语雀返回的错误信息
+#[derive(Deserialize, Default)]
+struct ErrorBody {
+    message: Option<String>,
+    code: Option<serde_json::Value>,
+}
+
+/// Check the status of the response, turning an error status into a `YuqueError`
+/// carrying the `message` and `code` of the error body.
+pub(crate) async fn ensure_success(
+    response: Response,
+    url: String,
+) -> Result<Response, YuqueError> {
+    let status = response.status();
+
+    if status == StatusCode::NOT_MODIFIED {
+        return Err(YuqueError::NotModified { url });
+    }
+
+    if !status.is_client_error() && !status.is_server_error() {
+        return Ok(response);
     }
+
+    let headers = response.headers().clone();
+    let body = response.text().await.unwrap_or_default();
+
+    Err(status_error(status.as_u16(), &headers, url, &body))
+}
+
+/// Check the status of the response and deserialize the body.
+pub(crate) async fn handle_response<T: DeserializeOwned>(
+    response: Response,
+    url: String,
+) -> Result<YuqueResponse<T>, YuqueError> {
+    let body = ensure_success(response, url.clone()).await?.bytes().await?;
+
+    parse_body(url, &body)
+}
+
+/// Deserialize a successful response body, keeping the url of the request in the error.
+pub(crate) fn parse_body<T: DeserializeOwned>(url: String, body: &[u8]) -> Result<T, YuqueError> {
+    serde_json::from_slice(body).map_err(|source| {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(url, error = %source, "failed to parse the response");
+
+        YuqueError::Deserialize { url, source }
+    })
+}
+
+pub(crate) fn status_error(
+    status_code: u16,
+    headers: &HeaderMap,
+    url: String,
+    body: &str,
+) -> YuqueError {
+    let ErrorBody { message, code } = serde_json::from_str(body).unwrap_or_default();
+
+    let code = code.map(|code| match code {
+        serde_json::Value::String(code) => code,
+        code => code.to_string(),
+    });
+
+    let error = match status_code {
+        401 => YuqueError::InvalidUserInfo { url, message, code },
+        403 => YuqueError::NoPermission { url, message, code },
+        404 => YuqueError::NotFound { url, message, code },
+        413 => YuqueError::PayloadTooLarge { url, message, code },
+        429 => YuqueError::RateLimited {
+            url,
+            retry_after: retry_after(headers),
+        },
+        500.. => YuqueError::ServerException { url, message, code },
+        _ => YuqueError::InvalidParams { url, message, code },
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(status = status_code, error = %error, "request failed");
+
+    error
+}
+
+/// `Retry-After` 可以是秒数，也可以是 HTTP 日期
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some(
+        (at.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
 }
 
 pub(crate) fn gen_random_slug(len: usize) -> String {
-    rand::thread_rng()
-        .sample_iter(&rand::distributions::Alphanumeric)
-        .take(len)
-        .map(char::from)
-        .collect()
+    RandomSlug::new().generate(len)
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default)]
+/// 文档正文的格式
+///
+/// 语雀只支持 `lake`、`markdown`、`html` 三种格式，没有其他的导出格式。
+/// 以后可能会增加新的格式，`match` 时需要保留通配分支 `_ => ...`。
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum YuqueFormat {
     #[serde(rename = "lake")]
     Lake,
@@ -108,13 +232,17 @@ impl From<&YuqueFormat> for String {
 
 impl From<YuqueFormat> for &str {
     fn from(value: YuqueFormat) -> Self {
-        value.into()
+        match value {
+            YuqueFormat::Lake => "lake",
+            YuqueFormat::Markdown => "markdown",
+            YuqueFormat::Html => "html",
+        }
     }
 }
 
 impl From<&YuqueFormat> for &str {
     fn from(value: &YuqueFormat) -> Self {
-        value.into()
+        (*value).into()
     }
 }
 
@@ -130,60 +258,122 @@ impl Display for YuqueFormat {
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(unused)]
-#[serde(tag = "type")]
-pub enum Toc<'a> {
-    #[serde(rename = "META")]
-    Meta(TocMeta<'a>),
-    #[serde(rename = "DOC")]
-    Doc(TocDocItem<'a>),
-    #[serde(rename = "TITLE")]
-    Title(TocTitleItem<'a>),
-}
-
-#[derive(Deserialize, Debug)]
-pub struct TocMeta<'a> {
-    pub count: u32,
-    // pub display_level: Cow<'a, str>,
-    pub tail_type: Cow<'a, str>,
-    pub base_version_id: u32,
-    pub published: bool,
-    pub max_level: u32,
-    #[serde(with = "time_serde")]
-    pub last_updated_at: DateTime<Local>,
-    pub version_id: u32,
-}
-
-#[derive(Deserialize, Debug, Serialize)]
-#[allow(unused)]
-pub struct TocDocItem<'a> {
-    pub title: Cow<'a, str>,
-    pub uuid: Cow<'a, str>,
-    pub url: Cow<'a, str>,
-    pub prev_uuid: Cow<'a, str>,
-    pub sibling_uuid: Cow<'a, str>,
-    pub child_uuid: Cow<'a, str>,
-    pub parent_uuid: Cow<'a, str>,
-    pub doc_id: u32,
-    pub level: u32,
-    pub id: u32,
-    pub open_window: u32,
-    pub visible: u32,
-}
-
-#[derive(Deserialize, Debug, Serialize)]
-pub struct TocTitleItem<'a> {
-    pub title: Cow<'a, str>,
-    pub uuid: Cow<'a, str>,
-    pub url: Cow<'a, str>,
-    pub prev_uuid: Cow<'a, str>,
-    pub sibling_uuid: Cow<'a, str>,
-    pub child_uuid: Cow<'a, str>,
-    pub parent_uuid: Cow<'a, str>,
-    pub doc_id: Cow<'a, str>,
-    pub level: u32,
-    pub id: Cow<'a, str>,
-    pub open_window: u32,
-    pub visible: u32,
+#[cfg(test)]
+mod test {
+    use std::{error::Error, time::Duration};
+
+    use reqwest::{
+        header::{HeaderMap, RETRY_AFTER},
+        Method,
+    };
+    use serde_json::json;
+
+    use crate::{parse_body, status_error, MockBackend, Yuque, YuqueError, YuqueResponse};
+
+    #[test]
+    fn should_report_the_endpoint_of_an_unexpected_payload() -> Result<(), Box<dyn Error>> {
+        let mock = MockBackend::new().route(
+            Method::GET,
+            "/repos/lzzzt/sdk-test/docs",
+            200,
+            json!({ "data": { "unexpected": true } }),
+        );
+
+        let client = Yuque::builder()
+            .token("token".into())
+            .host("https://example.com".into())
+            .backend(mock)
+            .build()?;
+        let docs = client.docs();
+
+        let error = tokio_test::block_on(docs.list_with_repo("lzzzt/sdk-test")).unwrap_err();
+
+        assert!(matches!(&error, YuqueError::Deserialize { .. }));
+        assert!(error.to_string().contains("/repos/lzzzt/sdk-test/docs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_url_in_deserialize_error() {
+        let result: Result<YuqueResponse<Vec<u32>>, _> =
+            parse_body("/repos/lzzzt/sdk-test/docs".into(), br#"{"data":{}}"#);
+
+        let error = result.unwrap_err();
+
+        assert!(matches!(
+            &error,
+            YuqueError::Deserialize { url, .. } if url == "/repos/lzzzt/sdk-test/docs"
+        ));
+        assert!(error.to_string().contains("/repos/lzzzt/sdk-test/docs"));
+    }
+
+    #[test]
+    fn should_read_message_and_code_from_error_body() {
+        let body = r#"{"status":403,"message":"You don't have permission","code":"forbidden"}"#;
+
+        assert!(matches!(
+            status_error(403, &HeaderMap::new(), "/repos/lzzzt/sdk-test".into(), body),
+            YuqueError::NoPermission { url, message: Some(message), code: Some(code) }
+                if url == "/repos/lzzzt/sdk-test"
+                    && message == "You don't have permission"
+                    && code == "forbidden"
+        ));
+
+        assert!(matches!(
+            status_error(
+                502,
+                &HeaderMap::new(),
+                "/user".into(),
+                "<html>Bad Gateway</html>"
+            ),
+            YuqueError::ServerException {
+                message: None,
+                code: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_return_payload_too_large_for_oversized_uploads() {
+        let body = r#"{"status":413,"message":"file is too large"}"#;
+
+        assert!(matches!(
+            status_error(413, &HeaderMap::new(), "/attachments".into(), body),
+            YuqueError::PayloadTooLarge { url, message: Some(message), .. }
+                if url == "/attachments" && message == "file is too large"
+        ));
+    }
+
+    #[test]
+    fn should_return_rate_limited_with_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+
+        assert!(matches!(
+            status_error(429, &headers, "/user".into(), ""),
+            YuqueError::RateLimited { retry_after: Some(retry_after), .. }
+                if retry_after == Duration::from_secs(120)
+        ));
+
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+
+        assert!(matches!(
+            status_error(429, &headers, "/user".into(), ""),
+            YuqueError::RateLimited { retry_after: Some(retry_after), .. }
+                if retry_after == Duration::ZERO
+        ));
+
+        assert!(matches!(
+            status_error(429, &HeaderMap::new(), "/user".into(), ""),
+            YuqueError::RateLimited {
+                retry_after: None,
+                ..
+            }
+        ));
+    }
 }
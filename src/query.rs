@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+/// 请求的查询参数
+///
+/// 以 `impl Into<QueryParams>` 作为参数的方法（如带 `_query` 后缀的方法）既可以传入
+/// 类型化的查询（如 [`crate::DocListQuery`]），也可以传入原始的键值对。
+///
+/// 原有的 `get_with_repo_ns`、`list_repo_of_user` 等方法仍然接受 `Option<&[(&str, &str)]>`，
+/// `Some(&[("raw", "1")])` 和 `None` 都能直接传入；泛型参数没法同时推断这两种写法，
+/// 所以这里不为 `Option<&[_; N]>` 实现 `From`。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::QueryParams;
+///
+/// let query = QueryParams::from(&[("raw", "1")]);
+/// assert_eq!(query.pairs(), &[("raw".to_string(), "1".to_string())]);
+///
+/// assert!(QueryParams::from(None).is_empty());
+/// ```
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    /// 创建空的查询参数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个查询参数
+    pub fn push(&mut self, key: impl Into<String>, value: impl ToString) -> &mut Self {
+        self.0.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// 所有的查询参数
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.0
+    }
+
+    /// 是否没有任何查询参数
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<(String, String)>> for QueryParams {
+    fn from(value: Vec<(String, String)>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&[(&str, &str)]> for QueryParams {
+    fn from(value: &[(&str, &str)]) -> Self {
+        Self(
+            value
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl<const N: usize> From<&[(&str, &str); N]> for QueryParams {
+    fn from(value: &[(&str, &str); N]) -> Self {
+        Self::from(&value[..])
+    }
+}
+
+impl From<Option<&[(&str, &str)]>> for QueryParams {
+    fn from(value: Option<&[(&str, &str)]>) -> Self {
+        value.map(Self::from).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryParams;
+
+    #[test]
+    fn should_convert_raw_pairs() {
+        let expected = QueryParams::from(vec![("raw".to_string(), "1".to_string())]);
+
+        assert_eq!(QueryParams::from(&[("raw", "1")]), expected);
+        assert_eq!(QueryParams::from(Some(&[("raw", "1")][..])), expected);
+        assert_eq!(QueryParams::from(&[]), QueryParams::new());
+        assert_eq!(QueryParams::from(None), QueryParams::new());
+
+        let mut query = QueryParams::new();
+        query.push("offset", 20).push("limit", 10);
+
+        assert_eq!(
+            query,
+            QueryParams::from(&[("offset", "20"), ("limit", "10")])
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use crate::YuqueError;
+
+/// 给引用类型实现其他整数宽度到 `Id(i32)` 的转换，
+/// 无损的宽度用 `From`，可能溢出的宽度用 `TryFrom`
+macro_rules! impl_id_conversions {
+    ($ty:ident) => {
+        impl_id_conversions!(@from $ty: i8, i16, u8, u16);
+        impl_id_conversions!(@try_from $ty: u32, i64, u64, usize);
+    };
+    (@from $ty:ident: $($int:ty),*) => {
+        $(
+            impl From<$int> for $ty {
+                fn from(value: $int) -> Self {
+                    Self::Id(value.into())
+                }
+            }
+        )*
+    };
+    (@try_from $ty:ident: $($int:ty),*) => {
+        $(
+            impl TryFrom<$int> for $ty {
+                type Error = YuqueError;
+
+                fn try_from(value: $int) -> Result<Self, Self::Error> {
+                    i32::try_from(value).map(Self::Id).map_err(|_| {
+                        YuqueError::invalid_params("", format!("id {value} is out of range of i32"))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+/// 仓库的引用，可以是命名空间（`login/book_slug`）或仓库编号
+///
+/// 编号以 `i32` 保存：`i8`/`i16`/`u8`/`u16` 可以直接转换，
+/// `u32`/`i64`/`u64`/`usize` 需要先 `RepoRef::try_from(id)?`，超出范围时返回 `YuqueError::InvalidParams`。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::RepoRef;
+///
+/// assert_eq!(RepoRef::from("lzzzt/sdk-test"), RepoRef::Namespace("lzzzt/sdk-test".into()));
+/// assert_eq!(RepoRef::from(1024), RepoRef::Id(1024));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoRef {
+    Namespace(String),
+    Id(i32),
+}
+
+impl From<&str> for RepoRef {
+    fn from(value: &str) -> Self {
+        Self::Namespace(value.into())
+    }
+}
+
+impl From<String> for RepoRef {
+    fn from(value: String) -> Self {
+        Self::Namespace(value)
+    }
+}
+
+impl From<&String> for RepoRef {
+    fn from(value: &String) -> Self {
+        Self::Namespace(value.clone())
+    }
+}
+
+impl From<i32> for RepoRef {
+    fn from(value: i32) -> Self {
+        Self::Id(value)
+    }
+}
+
+impl From<Cow<'_, str>> for RepoRef {
+    fn from(value: Cow<'_, str>) -> Self {
+        Self::Namespace(value.into_owned())
+    }
+}
+
+impl From<&Cow<'_, str>> for RepoRef {
+    fn from(value: &Cow<'_, str>) -> Self {
+        Self::Namespace(value.to_string())
+    }
+}
+
+impl From<&RepoRef> for RepoRef {
+    fn from(value: &RepoRef) -> Self {
+        value.clone()
+    }
+}
+
+impl_id_conversions!(RepoRef);
+
+impl RepoRef {
+    /// `/repos/:namespace`
+    pub(crate) fn path(&self) -> String {
+        format!("/repos/{}", self)
+    }
+
+    /// `/repos/:namespace/docs`
+    pub(crate) fn docs_path(&self) -> String {
+        format!("/repos/{}/docs", self)
+    }
+
+    /// `/repos/:namespace/docs/:slug`
+    pub(crate) fn doc_path(&self, doc: &DocRef) -> String {
+        format!("/repos/{}/docs/{}", self, doc)
+    }
+
+    /// `/repos/:namespace/docs/:slug/likes`
+    pub(crate) fn likes_path(&self, doc: &DocRef) -> String {
+        format!("{}/likes", self.doc_path(doc))
+    }
+
+    /// `/repos/:namespace/docs/:slug/share`
+    pub(crate) fn share_path(&self, doc: &DocRef) -> String {
+        format!("{}/share", self.doc_path(doc))
+    }
+
+    /// `/repos/:namespace/trash`
+    pub(crate) fn trash_path(&self) -> String {
+        format!("/repos/{}/trash", self)
+    }
+
+    /// `/repos/:namespace/toc`
+    #[cfg(feature = "toc")]
+    pub(crate) fn toc_path(&self) -> String {
+        format!("/repos/{}/toc", self)
+    }
+}
+
+impl Display for RepoRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoRef::Namespace(namespace) => write!(f, "{namespace}"),
+            RepoRef::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// 校验过的仓库命名空间 `login/slug`
+///
+/// 格式错误的命名空间（比如缺少 `/`）在本地就会返回 `YuqueError::InvalidParams`，
+/// 不用等到请求失败。`login` 和 `slug` 只能包含字母、数字、`-`、`_` 和 `.`。
+/// 实现了 `Display`，可以用在接受 `impl Into<RepoRef>` 或 `impl ToString` 的地方。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{Namespace, RepoRef};
+///
+/// let namespace = Namespace::parse("lzzzt/sdk-test").unwrap();
+///
+/// assert_eq!(namespace.login(), "lzzzt");
+/// assert_eq!(namespace.slug(), "sdk-test");
+/// assert_eq!(RepoRef::from(namespace), RepoRef::Namespace("lzzzt/sdk-test".into()));
+///
+/// assert!(Namespace::parse("sdk-test").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Namespace {
+    login: String,
+    slug: String,
+}
+
+impl Namespace {
+    /// 解析并校验 `login/slug`
+    pub fn parse(namespace: impl AsRef<str>) -> Result<Self, YuqueError> {
+        let raw = namespace.as_ref();
+
+        // 还没有对应的接口，url 留空，命名空间只放在 message 里
+        let invalid = |message: &str| {
+            YuqueError::invalid_params("", format!("invalid namespace {raw:?}, {message}"))
+        };
+
+        let (login, slug) = raw
+            .split_once('/')
+            .ok_or_else(|| invalid("expected login/slug"))?;
+
+        let is_valid = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+
+        if !is_valid(login) || !is_valid(slug) {
+            return Err(invalid(
+                "login and slug must be non-empty and only contain letters, digits, '-', '_' or '.'",
+            ));
+        }
+
+        Ok(Self {
+            login: login.into(),
+            slug: slug.into(),
+        })
+    }
+
+    /// 用户或团队的 login
+    pub fn login(&self) -> &str {
+        &self.login
+    }
+
+    /// 仓库的 slug
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+}
+
+impl FromStr for Namespace {
+    type Err = YuqueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.login, self.slug)
+    }
+}
+
+impl From<Namespace> for RepoRef {
+    fn from(value: Namespace) -> Self {
+        Self::Namespace(value.to_string())
+    }
+}
+
+impl From<&Namespace> for RepoRef {
+    fn from(value: &Namespace) -> Self {
+        Self::Namespace(value.to_string())
+    }
+}
+
+/// 文档的引用，可以是文档 slug 或文档编号
+///
+/// 编号的转换规则和 [`RepoRef`] 一样，`u32`/`i64`/`u64`/`usize` 需要先 `DocRef::try_from(id)?`。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::DocRef;
+///
+/// assert_eq!(DocRef::from("create-by-sdk"), DocRef::Slug("create-by-sdk".into()));
+/// assert_eq!(DocRef::from(1024), DocRef::Id(1024));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocRef {
+    Slug(String),
+    Id(i32),
+}
+
+impl From<&str> for DocRef {
+    fn from(value: &str) -> Self {
+        Self::Slug(value.into())
+    }
+}
+
+impl From<String> for DocRef {
+    fn from(value: String) -> Self {
+        Self::Slug(value)
+    }
+}
+
+impl From<&String> for DocRef {
+    fn from(value: &String) -> Self {
+        Self::Slug(value.clone())
+    }
+}
+
+impl From<i32> for DocRef {
+    fn from(value: i32) -> Self {
+        Self::Id(value)
+    }
+}
+
+impl From<Cow<'_, str>> for DocRef {
+    fn from(value: Cow<'_, str>) -> Self {
+        Self::Slug(value.into_owned())
+    }
+}
+
+impl From<&Cow<'_, str>> for DocRef {
+    fn from(value: &Cow<'_, str>) -> Self {
+        Self::Slug(value.to_string())
+    }
+}
+
+impl From<&DocRef> for DocRef {
+    fn from(value: &DocRef) -> Self {
+        value.clone()
+    }
+}
+
+impl_id_conversions!(DocRef);
+
+impl Display for DocRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocRef::Slug(slug) => write!(f, "{slug}"),
+            DocRef::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::{DocRef, Namespace, RepoRef};
+    use crate::YuqueError;
+
+    #[test]
+    fn should_build_repo_ref_both_ways() {
+        let namespace = String::from("lzzzt/sdk-test");
+
+        assert_eq!(
+            RepoRef::from(&namespace),
+            RepoRef::Namespace(namespace.clone())
+        );
+        assert_eq!(RepoRef::from(namespace).to_string(), "lzzzt/sdk-test");
+        assert_eq!(RepoRef::from(1024).to_string(), "1024");
+    }
+
+    #[test]
+    fn should_parse_namespace() -> Result<(), YuqueError> {
+        let namespace: Namespace = "lzzzt/sdk-test".parse()?;
+
+        assert_eq!(namespace.to_string(), "lzzzt/sdk-test");
+        assert_eq!(
+            RepoRef::from(&namespace),
+            RepoRef::Namespace("lzzzt/sdk-test".into())
+        );
+
+        for invalid in ["sdk-test", "/sdk-test", "lzzzt/", "a/b/c", "lzzzt/sdk test"] {
+            assert!(matches!(
+                Namespace::parse(invalid),
+                Err(YuqueError::InvalidParams { url, message: Some(message), .. })
+                    if url.is_empty() && message.contains(&format!("{invalid:?}"))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_build_doc_ref_both_ways() {
+        assert_eq!(
+            DocRef::from(String::from("create-by-sdk")),
+            DocRef::Slug("create-by-sdk".into())
+        );
+        assert_eq!(DocRef::from("create-by-sdk").to_string(), "create-by-sdk");
+        assert_eq!(DocRef::from(1024).to_string(), "1024");
+    }
+
+    #[test]
+    fn should_build_refs_from_cow() {
+        let namespace: Cow<str> = Cow::Borrowed("lzzzt/sdk-test");
+        let slug: Cow<str> = Cow::Owned("create-by-sdk".into());
+
+        assert_eq!(
+            RepoRef::from(&namespace),
+            RepoRef::Namespace("lzzzt/sdk-test".into())
+        );
+        assert_eq!(RepoRef::from(namespace).to_string(), "lzzzt/sdk-test");
+        assert_eq!(DocRef::from(&slug), DocRef::Slug("create-by-sdk".into()));
+        assert_eq!(DocRef::from(slug).to_string(), "create-by-sdk");
+    }
+
+    #[test]
+    fn should_build_refs_from_other_integer_widths() -> Result<(), YuqueError> {
+        assert_eq!(RepoRef::from(1024u16), RepoRef::Id(1024));
+        assert_eq!(DocRef::from(8u8), DocRef::Id(8));
+        assert_eq!(RepoRef::try_from(1024u64)?, RepoRef::Id(1024));
+        assert_eq!(DocRef::try_from(1024i64)?, DocRef::Id(1024));
+        assert_eq!(DocRef::try_from(1024usize)?, DocRef::Id(1024));
+
+        assert!(matches!(
+            RepoRef::try_from(u32::MAX),
+            Err(YuqueError::InvalidParams { message: Some(message), .. })
+                if message.contains(&u32::MAX.to_string())
+        ));
+        assert!(DocRef::try_from(i64::MIN).is_err());
+
+        Ok(())
+    }
+}
@@ -1,42 +1,51 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
 
 use chrono::{DateTime, Local};
 use derive_builder::Builder;
+use reqwest::Response;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    gen_random_slug, judge_status_code, serde::toc_serde, time_serde, Toc, User, Yuque, YuqueError,
-    YuqueResponse,
+    ensure_success, gen_random_slug, handle_response, time_serde, Doc, QueryParams, RepoRef,
+    SlugGenerator, User, Yuque, YuqueError, YuqueResponse, REPO_SLUG_LEN,
 };
+#[cfg(feature = "toc")]
+use crate::{serde::toc_serde, Toc, TocAction, TocActionMode, TocNode, TocNodeType};
 
 /// id - 仓库编号
-/// type - 类型 [Book - 文档]
+/// type - 类型 <RepoType> [Book - 文档, Design - 设计]
 /// slug - 仓库路径
 /// name - 名称
 /// namespace - 仓库完整路径 user.login/book.slug
 /// user_id - 所属的团队/用户编号
-/// user - <UserSerializer>
+/// user - <UserSerializer>，所属团队/用户已被删除时为空
 /// description - 介绍
 /// creator_id - 创建人 User Id
-/// public - 公开状态 [1 - 公开, 0 - 私密]
+/// public - 公开状态 <Visibility> [2 - 成员公开, 1 - 公开, 0 - 私密]
 /// likes_count - 喜欢数量
 /// watches_count - 订阅数量
 /// created_at - 创建时间
 /// updated_at - 更新时间
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct RepoListItem<'a> {
     pub id: i32,
     #[serde(rename = "type")]
-    pub book_type: Cow<'a, str>,
+    pub book_type: RepoType,
     pub slug: Cow<'a, str>,
     pub name: Cow<'a, str>,
     pub namespace: Cow<'a, str>,
     pub user_id: i32,
-    pub user: User<'a>,
+    pub user: Option<User<'a>>,
     pub description: Option<Cow<'a, str>>,
     pub creator_id: i32,
-    pub public: u8,
+    pub public: Visibility,
+    #[serde(default)]
     pub likes_count: i32,
+    #[serde(default)]
     pub watches_count: i32,
     #[serde(with = "time_serde")]
     pub created_at: DateTime<Local>,
@@ -44,6 +53,23 @@ pub struct RepoListItem<'a> {
     pub updated_at: DateTime<Local>,
 }
 
+/// 只按 `id` 计算哈希，见 [`User`] 的说明
+impl<'a> Hash for RepoListItem<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// 一行摘要：`namespace — name`，名称为空时只显示 `namespace`
+impl<'a> Display for RepoListItem<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name.trim() {
+            "" => write!(f, "{}", self.namespace),
+            name => write!(f, "{} — {name}", self.namespace),
+        }
+    }
+}
+
 /// id - 仓库编号
 /// type - 类型 [Book - 文档]
 /// slug - 仓库路径
@@ -52,15 +78,15 @@ pub struct RepoListItem<'a> {
 /// user_id - 所属的团队/用户编号
 /// user - <UserSerializer>
 /// description - 介绍
-/// toc_yml - 目录原文
+/// toc_yml - 目录原文，解析为 `toc`，需要 `toc` feature
 /// creator_id - 创建人 User Id
-/// public - 公开状态 [1 - 公开, 0 - 私密]
+/// public - 公开状态 <Visibility> [2 - 成员公开, 1 - 公开, 0 - 私密]
 /// items_count - 文档数量
 /// likes_count - 喜欢数量
 /// watches_count - 订阅数量
 /// created_at - 创建时间
 /// updated_at - 更新时间
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct RepoDetail<'a> {
     pub id: i32,
     #[serde(rename = "type")]
@@ -71,12 +97,16 @@ pub struct RepoDetail<'a> {
     pub user_id: i32,
     pub user: User<'a>,
     pub description: Option<Cow<'a, str>>,
-    #[serde(rename = "toc_yml", with = "toc_serde")]
+    #[cfg(feature = "toc")]
+    #[serde(rename = "toc_yml", default, with = "toc_serde")]
     pub toc: Option<Vec<Toc<'a>>>,
     pub creator_id: i32,
-    pub public: u8,
+    pub public: Visibility,
+    #[serde(default)]
     pub items_count: i32,
+    #[serde(default)]
     pub likes_count: i32,
+    #[serde(default)]
     pub watches_count: i32,
     #[serde(with = "time_serde")]
     pub created_at: DateTime<Local>,
@@ -84,31 +114,218 @@ pub struct RepoDetail<'a> {
     pub updated_at: DateTime<Local>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default)]
+/// 仓库的类型
+///
+/// 以后可能会增加新的类型，`match` 时需要保留通配分支 `_ => ...`。
+/// 还不认识的类型（如 `Sheet`）会解析为 `Other`，不会导致整个列表解析失败。
+/// 列表的筛选见 [`RepoTypeFilter`]，创建仓库见 [`CreateRepoType`]。
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RepoType {
     #[default]
+    Book,
+    Design,
+    #[serde(other)]
+    Other,
+}
+
+/// 创建仓库时可以使用的类型，只有语雀接受的 `Book` 和 `Design`
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{CreateRepoType, RepoType};
+///
+/// assert_eq!(CreateRepoType::try_from(RepoType::Design).unwrap(), CreateRepoType::Design);
+/// assert!(CreateRepoType::try_from(RepoType::Other).is_err());
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CreateRepoType {
+    #[default]
+    Book,
+    Design,
+}
+
+impl TryFrom<RepoType> for CreateRepoType {
+    type Error = YuqueError;
+
+    fn try_from(value: RepoType) -> Result<Self, Self::Error> {
+        match value {
+            RepoType::Book => Ok(CreateRepoType::Book),
+            RepoType::Design => Ok(CreateRepoType::Design),
+            other => Err(YuqueError::invalid_params(
+                "",
+                format!("can't create a repo of type {other:?}, expected Book or Design"),
+            )),
+        }
+    }
+}
+
+impl From<CreateRepoType> for RepoType {
+    fn from(value: CreateRepoType) -> Self {
+        match value {
+            CreateRepoType::Book => RepoType::Book,
+            CreateRepoType::Design => RepoType::Design,
+        }
+    }
+}
+
+/// 获取仓库列表时按类型筛选，`All` 为全部类型
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum RepoTypeFilter {
     Book,
     Design,
     #[serde(rename = "all")]
     All,
 }
 
+/// 仓库的公开状态，与语雀接口中的 `public` 数字互相转换
+///
+/// * `Private` - 私密，对应 0
+/// * `Public` - 公开，对应 1
+/// * `MembersOnly` - 成员公开，对应 2
+///
+/// 其他数字在反序列化时会报错。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::Visibility;
+///
+/// assert_eq!(serde_json::to_string(&Visibility::MembersOnly).unwrap(), "2");
+/// assert_eq!(serde_json::from_str::<Visibility>("0").unwrap(), Visibility::Private);
+/// assert!(serde_json::from_str::<Visibility>("5").is_err());
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Visibility {
+    Private,
+    #[default]
+    Public,
+    MembersOnly,
+}
+
+impl TryFrom<u8> for Visibility {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Visibility::Private),
+            1 => Ok(Visibility::Public),
+            2 => Ok(Visibility::MembersOnly),
+            _ => Err(format!(
+                "invalid visibility {value}, expected 0 (private), 1 (public) or 2 (members only)"
+            )),
+        }
+    }
+}
+
+impl From<Visibility> for u8 {
+    fn from(value: Visibility) -> Self {
+        match value {
+            Visibility::Private => 0,
+            Visibility::Public => 1,
+            Visibility::MembersOnly => 2,
+        }
+    }
+}
+
+impl RepoTypeFilter {
+    /// 与序列化结果相同的字符串
+    fn as_str(&self) -> &'static str {
+        match self {
+            RepoTypeFilter::Book => "Book",
+            RepoTypeFilter::Design => "Design",
+            RepoTypeFilter::All => "all",
+        }
+    }
+}
+
+/// 获取仓库列表的查询参数
+///
+/// # Fields
+/// * `repo_type: Option<RepoTypeFilter>` - 仓库类型，对应 `type`
+/// * `offset: Option<u32>` - 跳过的仓库数
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{QueryParams, RepoListQuery, RepoTypeFilter};
+///
+/// let query = RepoListQuery::new().repo_type(RepoTypeFilter::Design).offset(20);
+///
+/// assert_eq!(
+///     QueryParams::from(query),
+///     QueryParams::from(&[("type", "Design"), ("offset", "20")])
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RepoListQuery {
+    pub repo_type: Option<RepoTypeFilter>,
+    pub offset: Option<u32>,
+}
+
+impl RepoListQuery {
+    /// 创建仓库列表的查询参数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只列出某种类型的仓库
+    pub fn repo_type(mut self, repo_type: RepoTypeFilter) -> Self {
+        self.repo_type = Some(repo_type);
+        self
+    }
+
+    /// 跳过前 `offset` 个仓库
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl From<RepoListQuery> for QueryParams {
+    fn from(value: RepoListQuery) -> Self {
+        let mut query = QueryParams::new();
+
+        if let Some(repo_type) = value.repo_type {
+            query.push("type", repo_type.as_str());
+        }
+
+        if let Some(offset) = value.offset {
+            query.push("offset", offset);
+        }
+
+        query
+    }
+}
+
 /// * `name` - 仓库名称
 /// * `slug` - 仓库路径
 /// * `description` - 仓库介绍
-/// * `public` - 公开状态 [2 - 成员公开, 1 - 公开, 0 - 私密]
-/// * `book_type` - 仓库类型 [Book - 文档, Design - 设计]
-#[derive(Debug, Serialize, Deserialize, Builder)]
+/// * `public` - 公开状态 <Visibility> [2 - 成员公开, 1 - 公开, 0 - 私密]
+/// * `book_type` - 仓库类型 <CreateRepoType> [Book - 文档, Design - 设计]
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{Repo, Visibility};
+///
+/// let repo = Repo {
+///     name: "repo".into(),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(repo.public, Visibility::Public);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Builder, PartialEq, Eq)]
 pub struct Repo {
     pub name: String,
-    #[builder(default = "gen_random_slug(6)")]
+    #[builder(default = "gen_random_slug(REPO_SLUG_LEN)")]
     pub slug: String,
     #[builder(default = "String::new()")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
-    #[builder(default = "1")]
-    pub public: u8,
-    #[builder(default = "RepoType::Book")]
-    pub book_type: RepoType,
+    #[builder(default)]
+    pub public: Visibility,
+    #[builder(default = "CreateRepoType::Book")]
+    pub book_type: CreateRepoType,
 }
 
 impl Repo {
@@ -117,6 +334,27 @@ impl Repo {
     }
 }
 
+impl RepoBuilder {
+    /// 用给定的策略生成 slug，见 [`SlugGenerator`]
+    pub fn slug_with(&mut self, generator: &mut impl SlugGenerator) -> &mut Self {
+        self.slug = Some(generator.generate(REPO_SLUG_LEN));
+        self
+    }
+}
+
+impl Default for Repo {
+    /// 与 `Repo::builder()` 的默认值保持一致
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            slug: gen_random_slug(REPO_SLUG_LEN),
+            description: String::new(),
+            public: Visibility::Public,
+            book_type: CreateRepoType::Book,
+        }
+    }
+}
+
 impl<'a> TryFrom<RepoDetail<'a>> for Repo {
     type Error = YuqueError;
 
@@ -129,11 +367,154 @@ impl<'a> TryFrom<RepoDetail<'a>> for Repo {
                 .map(|s| s.into_owned())
                 .unwrap_or_default(),
             public: value.public,
-            book_type: value.book_type,
+            book_type: value.book_type.try_into()?,
         })
     }
 }
 
+/// 用于部分更新的仓库，只会提交设置了的字段
+///
+/// # Fields
+///
+/// * `name: Option<String>` - 仓库名称
+/// * `slug: Option<String>` - 仓库路径，修改后命名空间也会改变
+/// * `description: Option<String>` - 仓库介绍
+/// * `public: Option<Visibility>` - 公开状态
+///
+/// 从 [`Repo`] 转换时空的 `name`、`slug`、`description` 不会提交，不会清空服务端已有的值；
+/// 但 `Repo::builder()` 没有设置 `slug` 时会生成随机的 slug，转换后会把仓库路径改成它，
+/// 不想修改路径时请直接使用 `UpdateRepo`。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::UpdateRepo;
+///
+/// let update = UpdateRepo::builder().name("x").build().unwrap();
+///
+/// assert_eq!(serde_json::to_string(&update).unwrap(), r#"{"name":"x"}"#);
+/// ```
+#[derive(Builder, Serialize, Clone, Default, Debug, PartialEq, Eq)]
+#[builder(default, setter(into, strip_option))]
+pub struct UpdateRepo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<Visibility>,
+}
+
+impl UpdateRepo {
+    /// 创建一个部分更新
+    pub fn builder() -> UpdateRepoBuilder {
+        UpdateRepoBuilder::default()
+    }
+}
+
+impl From<Repo> for UpdateRepo {
+    fn from(value: Repo) -> Self {
+        let non_empty = |s: String| (!s.is_empty()).then_some(s);
+
+        UpdateRepo {
+            name: non_empty(value.name),
+            slug: non_empty(value.slug),
+            description: non_empty(value.description),
+            public: Some(value.public),
+        }
+    }
+}
+
+/// 复制仓库的进度
+///
+/// * `done` - 已处理（成功或失败）的文档数量
+/// * `total` - 需要复制的文档总数
+#[derive(Debug, Clone, Copy)]
+pub struct CloneProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// 复制仓库的结果
+///
+/// * `repo` - 新创建的仓库
+/// * `copied` - 复制成功的文档 slug
+/// * `failed` - 复制失败的文档 slug（分组为它的标题）以及对应的错误
+#[derive(Debug)]
+pub struct CloneRepoReport<'a> {
+    pub repo: RepoDetail<'a>,
+    pub copied: Vec<String>,
+    pub failed: Vec<(String, YuqueError)>,
+}
+
+/// 复制仓库时要重建的一项，`parent` 是父节点在列表中的下标
+struct CloneStep {
+    parent: Option<usize>,
+    node: CloneNode,
+}
+
+/// 文档（slug）或分组（标题）
+enum CloneNode {
+    Doc(String),
+    #[cfg_attr(not(feature = "toc"), allow(dead_code))]
+    Title(String),
+}
+
+/// 按目录的先序排列的节点，父节点总在子节点之前，没有目录时为 `None`
+#[cfg(feature = "toc")]
+fn clone_steps(repo: &RepoDetail) -> Option<Vec<CloneStep>> {
+    fn walk(nodes: Vec<TocNode>, parent: Option<usize>, steps: &mut Vec<CloneStep>) {
+        for node in nodes {
+            let item = match &node.item {
+                Toc::Doc(doc) => CloneNode::Doc(doc.url.to_string()),
+                Toc::Title(title) => CloneNode::Title(title.title.to_string()),
+                Toc::Meta(_) => continue,
+            };
+
+            steps.push(CloneStep { parent, node: item });
+            walk(node.children, Some(steps.len() - 1), steps);
+        }
+    }
+
+    repo.toc.as_ref().map(|toc| {
+        let mut steps = Vec::new();
+        walk(Toc::into_tree(toc.clone()), None, &mut steps);
+        steps
+    })
+}
+
+#[cfg(not(feature = "toc"))]
+fn clone_steps(_repo: &RepoDetail) -> Option<Vec<CloneStep>> {
+    None
+}
+
+/// `parent` 节点（为 `None` 时是根节点）下最后一个子节点的 uuid
+#[cfg(feature = "toc")]
+fn last_child_uuid(nodes: &[TocNode], parent: Option<&str>) -> Option<String> {
+    let Some(parent) = parent else {
+        return nodes.last()?.item.uuid().map(str::to_string);
+    };
+
+    nodes.iter().find_map(|node| {
+        if node.item.uuid() == Some(parent) {
+            node.children.last()?.item.uuid().map(str::to_string)
+        } else {
+            last_child_uuid(&node.children, Some(parent))
+        }
+    })
+}
+
+/// `/users/:login/repos`
+pub(crate) fn user_repos_path(user: impl ToString) -> String {
+    format!("/users/{}/repos", user.to_string())
+}
+
+/// `/groups/:login/repos`
+pub(crate) fn group_repos_path(group: impl ToString) -> String {
+    format!("/groups/{}/repos", group.to_string())
+}
+
 pub struct ReposClient {
     pub(crate) client: Yuque,
 }
@@ -147,7 +528,7 @@ impl ReposClient {
     /// * `data` - 查询参数
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -157,7 +538,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let repos = yuque.repos().list_repo_of_user("username", None).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let repos = repos.list_repo_of_user("username", None).await?;
     ///
     ///     println!("{:?}", repos);
     ///     Ok(())
@@ -167,16 +550,50 @@ impl ReposClient {
         &self,
         user: impl ToString,
         data: Option<&[(&str, &str)]>,
-    ) -> Result<YuqueResponse<Vec<RepoListItem>>, YuqueError> {
-        let url = format!("/users/{}/repos", user.to_string());
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list_repo_of_user_query(user, data).await
+    }
 
-        let data = data.unwrap_or_default();
+    /// List repo of user with query params
+    /// 带查询参数获取用户的仓库列表
+    ///
+    /// # Arguments
+    /// * `user` - 用户名/id
+    /// * `query` - 查询参数，推荐使用 [`RepoListQuery`]
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{RepoListQuery, RepoTypeFilter, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let query = RepoListQuery::new().repo_type(RepoTypeFilter::Design);
+    ///
+    ///     let repos = repos.list_repo_of_user_query("username", query).await?;
+    ///
+    ///     println!("{:?}", repos);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_repo_of_user_query(
+        &self,
+        user: impl ToString,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        let url = user_repos_path(user);
 
-        let response = self.client.get(&url)?.query(&data).send().await?;
+        let request = self.client.get(&url)?.query(&query.into());
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let response = self.client.execute(request).await?;
 
-        Ok(response.json().await?)
+        handle_response(response, url).await
     }
 
     /// List repo of group
@@ -187,7 +604,7 @@ impl ReposClient {
     /// * `data` - 查询参数
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -197,7 +614,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let repos = yuque.repos().list_repo_of_group("group name", None).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let repos = repos.list_repo_of_group("group name", None).await?;
     ///
     ///     println!("{:?}", repos);
     ///     Ok(())
@@ -207,16 +626,123 @@ impl ReposClient {
         &self,
         group: impl ToString,
         data: Option<&[(&str, &str)]>,
-    ) -> Result<YuqueResponse<Vec<RepoListItem>>, YuqueError> {
-        let url = format!("/groups/{}/repos", group.to_string());
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        self.list_repo_of_group_query(group, data).await
+    }
+
+    /// List repo of group with query params
+    /// 带查询参数获取团队的仓库列表
+    ///
+    /// # Arguments
+    /// * `group` - 团队名/id
+    /// * `query` - 查询参数，推荐使用 [`RepoListQuery`]
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{RepoListQuery, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let query = RepoListQuery::new().offset(20);
+    ///
+    ///     let repos = repos.list_repo_of_group_query("group name", query).await?;
+    ///
+    ///     println!("{:?}", repos);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_repo_of_group_query(
+        &self,
+        group: impl ToString,
+        query: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<RepoListItem<'_>>>, YuqueError> {
+        let url = group_repos_path(group);
 
-        let data = data.unwrap_or_default();
+        let request = self.client.get(&url)?.query(&query.into());
 
-        let response = self.client.get(&url)?.query(&data).send().await?;
+        let response = self.client.execute(request).await?;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        handle_response(response, url).await
+    }
 
-        Ok(response.json().await?)
+    /// Count the repos of user
+    /// 获取用户的仓库总数
+    ///
+    /// 和 [`crate::DocsClient::count_with_repo`] 一样只请求一条数据并读取 `meta.total`，
+    /// 服务端没有返回总数时为 `None`。
+    ///
+    /// # Arguments
+    /// * `user` - 用户名/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let count = yuque.repos().count_repo_of_user("username").await?;
+    ///
+    ///     println!("{:?}", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_repo_of_user(&self, user: impl ToString) -> Result<Option<u32>, YuqueError> {
+        self.count_repos(user_repos_path(user)).await
+    }
+
+    /// Count the repos of group
+    /// 获取团队的仓库总数，见 [`ReposClient::count_repo_of_user`]
+    ///
+    /// # Arguments
+    /// * `group` - 团队名/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let count = yuque.repos().count_repo_of_group("group name").await?;
+    ///
+    ///     println!("{:?}", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_repo_of_group(
+        &self,
+        group: impl ToString,
+    ) -> Result<Option<u32>, YuqueError> {
+        self.count_repos(group_repos_path(group)).await
+    }
+
+    async fn count_repos(&self, url: String) -> Result<Option<u32>, YuqueError> {
+        let request = self
+            .client
+            .get(&url)?
+            .query(&[("offset", "0"), ("limit", "1")]);
+
+        let response = self.client.execute(request).await?;
+
+        let response: YuqueResponse<Vec<RepoListItem>> = handle_response(response, url).await?;
+
+        Ok(response.total())
     }
 
     /// create repo of user
@@ -227,8 +753,8 @@ impl ReposClient {
     /// * `data` - 仓库信息
     ///
     /// # Example
-    /// ```rust
-    /// use yuque_rust::Yuque;
+    /// ```rust,no_run
+    /// use yuque_rust::{Repo, Yuque};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -237,7 +763,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let repo = yuque.repos().create_repo_of_user("username", Repo::builder().name("test").build()).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let repo = repos.create_repo_of_user("username", Repo::builder().name("test".into()).build()?).await?;
     ///
     ///     println!("{:?}", repo);
     ///     Ok(())
@@ -247,16 +775,16 @@ impl ReposClient {
         &self,
         user: impl ToString,
         data: Repo,
-    ) -> Result<YuqueResponse<RepoDetail>, YuqueError> {
-        let url = format!("/users/{}/repos", user.to_string());
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let url = user_repos_path(user);
 
         let data = serde_json::to_string(&data).ok();
 
-        let response = self.client.post(&url, data)?.send().await?;
+        let request = self.client.post(&url, data)?;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let response = self.client.execute(request).await?;
 
-        Ok(response.json().await?)
+        handle_response(response, url).await
     }
 
     /// create repo of group
@@ -267,8 +795,8 @@ impl ReposClient {
     /// * `data` - 仓库信息
     ///
     /// # Example
-    /// ```rust
-    /// use yuque_rust::Yuque;
+    /// ```rust,no_run
+    /// use yuque_rust::{Repo, Yuque};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -277,7 +805,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///     
-    ///     let repo = yuque.repos().create_repo_of_group("group name", Repo::builder().name("test").build()).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let repo = repos.create_repo_of_group("group name", Repo::builder().name("test".into()).build()?).await?;
     ///     
     ///     println!("{:?}", repo);
     ///     Ok(())
@@ -287,27 +817,27 @@ impl ReposClient {
         &self,
         group: impl ToString,
         data: Repo,
-    ) -> Result<YuqueResponse<RepoDetail>, YuqueError> {
-        let url = format!("/groups/{}/repos", group.to_string());
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let url = group_repos_path(group);
 
         let data = serde_json::to_string(&data).ok();
 
-        let response = self.client.post(&url, data)?.send().await?;
+        let request = self.client.post(&url, data)?;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let response = self.client.execute(request).await?;
 
-        Ok(response.json().await?)
+        handle_response(response, url).await
     }
 
     /// get repo
     /// 获取仓库信息
     ///
     /// # Arguments
-    /// * `repo` - 仓库名/id
+    /// * `repo` - 仓库的命名空间/id
     /// * `data` - 查询参数
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -317,7 +847,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let repo = yuque.repos().get("username/repo name", None).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let repo = repos.get("username/repo name", None).await?;
     ///     
     ///     println!("{:?}", repo);
     ///     Ok(())
@@ -325,31 +857,171 @@ impl ReposClient {
     /// ```
     pub async fn get(
         &self,
-        repo: impl ToString,
+        repo: impl Into<RepoRef>,
         data: Option<&[(&str, &str)]>,
-    ) -> Result<YuqueResponse<RepoDetail>, YuqueError> {
-        let url = format!("/repos/{}", repo.to_string());
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
 
-        let response = self.client.get(&url)?.query(&data).send().await?;
+        let request = self.client.get(&url)?.query(&QueryParams::from(data));
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let response = self.client.execute(request).await?;
 
-        // let text = dbg!(response.text().await.unwrap());
+        handle_response(response, url).await
+    }
 
-        // Ok(serde_json::from_str(&text).unwrap())
-        Ok(response.json().await?)
+    /// get repo without parsing the body
+    /// 获取仓库信息的原始响应
+    ///
+    /// 与 `get` 相同，但只检查状态码，直接返回 `reqwest::Response`
+    ///
+    /// # Arguments
+    /// * `repo` - 仓库的命名空间/id
+    /// * `data` - 查询参数
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let response = repos.get_raw("username/repo name", None).await?;
+    ///
+    ///     println!("{}", response.status());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_raw(
+        &self,
+        repo: impl Into<RepoRef>,
+        data: impl Into<QueryParams>,
+    ) -> Result<Response, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
+
+        let request = self.client.get(&url)?.query(&data.into());
+
+        let response = self.client.execute(request).await?;
+
+        ensure_success(response, url).await
+    }
+
+    /// get the toc of repo
+    /// 获取仓库的目录
+    ///
+    /// 直接请求 `/repos/:namespace/toc`，服务端返回的是 JSON 数组，不需要再解析 `toc_yml`。
+    ///
+    /// # Arguments
+    /// * `repo` - 仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{Toc, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let toc = repos.get_toc("username/repo name").await?;
+    ///
+    ///     for node in Toc::into_tree(toc.data) {
+    ///         println!("{:?}", node.item.title());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "toc")]
+    pub async fn get_toc(
+        &self,
+        repo: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<Vec<Toc<'_>>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.toc_path();
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// update the toc of repo
+    /// 修改仓库的目录
+    ///
+    /// 支持插入、修改、移动和删除目录节点，见 [`TocAction`]，返回修改后的目录。
+    ///
+    /// # Arguments
+    /// * `repo` - 仓库的命名空间/id
+    /// * `action` - 目录操作
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::{TocAction, TocActionMode, TocNodeType, Yuque};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let action = TocAction::AppendNode {
+    ///         action_mode: TocActionMode::Child,
+    ///         target_uuid: None,
+    ///         node_type: TocNodeType::Title,
+    ///         title: Some("Chapter 1".into()),
+    ///         url: None,
+    ///         doc_ids: Vec::new(),
+    ///     };
+    ///
+    ///     let toc = repos.update_toc("username/repo name", action).await?;
+    ///
+    ///     println!("{}", toc.data.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "toc")]
+    pub async fn update_toc(
+        &self,
+        repo: impl Into<RepoRef>,
+        action: TocAction,
+    ) -> Result<YuqueResponse<Vec<Toc<'_>>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.toc_path();
+
+        let data = serde_json::to_string(&action).ok();
+
+        let request = self.client.put(&url, data)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
     }
 
     /// update repo
     /// 更新仓库信息
     ///
     /// # Arguments
-    /// * `repo` - 仓库名/id
-    /// * `data` - 仓库信息
+    /// * `repo` - 仓库的命名空间/id
+    /// * `data` - 要修改的仓库信息，[`UpdateRepo`] 或 [`Repo`]，只提交非空的字段
     ///
     /// # Example
-    /// ```rust
-    /// use yuque_rust::Yuque;
+    /// ```rust,no_run
+    /// use yuque_rust::{UpdateRepo, Yuque};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -358,7 +1030,9 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let response = yuque.repos().update("username/repo name", Repo::builder().name("test").build()).await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let response = repos.update("username/repo name", UpdateRepo::builder().name("test").build()?).await?;
     ///
     ///     println!("{:?}", response);
     ///     Ok(())
@@ -366,28 +1040,29 @@ impl ReposClient {
     /// ```   
     pub async fn update(
         &self,
-        repo: impl ToString,
-        data: Repo,
-    ) -> Result<YuqueResponse<RepoDetail>, YuqueError> {
-        let url = format!("/repos/{}", repo.to_string());
+        repo: impl Into<RepoRef>,
+        data: impl Into<UpdateRepo>,
+    ) -> Result<YuqueResponse<RepoDetail<'_>>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
 
-        let data = serde_json::to_string(&data).ok();
+        let data = serde_json::to_string(&data.into()).ok();
 
-        let response = self.client.put(&url, data)?.send().await?;
+        let request = self.client.put(&url, data)?;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        let response = self.client.execute(request).await?;
 
-        Ok(response.json().await?)
+        handle_response(response, url).await
     }
 
     /// delete repo
     /// 删除仓库
     ///
     /// # Arguments
-    /// * `repo` - 仓库名/id
+    /// * `repo` - 仓库的命名空间/id
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
     /// use yuque_rust::Yuque;
     ///
     /// #[tokio::main]
@@ -397,19 +1072,662 @@ impl ReposClient {
     ///                         .host("https://www.yuque.com".to_string())
     ///                         .build()?;
     ///
-    ///     let response = yuque.repos().delete("username/repo name").await?;
+    ///     let repos = yuque.repos();
+    ///
+    ///     let response = repos.delete("username/repo name").await?;
     ///
     ///     println!("{:?}", response);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete(&self, repo: impl ToString) -> Result<(), YuqueError> {
-        let url = format!("/repos/{}", repo.to_string());
+    pub async fn delete(&self, repo: impl Into<RepoRef>) -> Result<(), YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = repo.path();
+
+        let request = self.client.delete(&url)?;
 
-        let response = self.client.delete(&url)?.send().await?;
+        let response = self.client.execute(request).await?;
 
-        judge_status_code(response.status().as_u16(), url)?;
+        ensure_success(response, url).await?;
 
         Ok(())
     }
+
+    /// clone repo
+    /// 复制仓库到指定用户下
+    ///
+    /// 按源仓库目录中的顺序逐篇复制文档，并用新文档的 id 和分组的标题按原来的层级重建目录；
+    /// 源仓库没有目录时按文档列表的顺序复制，也不会创建目录。
+    /// 单篇文档失败不会中断整个过程，失败的文档会记录在返回的 `CloneRepoReport` 中，
+    /// 它的子节点会挂到最近一个复制成功的祖先节点下。
+    /// 源仓库的类型不是 `Book` 或 `Design` 时不会创建新仓库，直接返回 `YuqueError::InvalidParams`。
+    ///
+    /// # Arguments
+    /// * `source` - 源仓库的命名空间/id
+    /// * `user` - 目标用户名/id
+    /// * `name` - 新仓库的名称
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let repos = yuque.repos();
+    ///
+    ///     let report = repos.clone_repo("username/template", "username", "new project").await?;
+    ///
+    ///     println!("{:?}", report.failed);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn clone_repo(
+        &self,
+        source: impl Into<RepoRef>,
+        user: impl ToString,
+        name: impl ToString,
+    ) -> Result<CloneRepoReport<'_>, YuqueError> {
+        self.clone_repo_with_progress(source, user, name, |_| ())
+            .await
+    }
+
+    /// clone repo and report the progress
+    /// 复制仓库到指定用户下，每处理完一篇文档都会调用一次 `on_progress`
+    ///
+    /// # Arguments
+    /// * `source` - 源仓库的命名空间/id
+    /// * `user` - 目标用户名/id
+    /// * `name` - 新仓库的名称
+    /// * `on_progress` - 进度回调
+    pub async fn clone_repo_with_progress(
+        &self,
+        source: impl Into<RepoRef>,
+        user: impl ToString,
+        name: impl ToString,
+        mut on_progress: impl FnMut(CloneProgress),
+    ) -> Result<CloneRepoReport<'_>, YuqueError> {
+        let source: RepoRef = source.into();
+        let docs = self.client.docs();
+
+        let source_repo = self.get(&source, None).await?.data;
+
+        let (steps, rebuild_toc) = match clone_steps(&source_repo) {
+            Some(steps) => (steps, true),
+            None => {
+                let steps = docs
+                    .list_with_repo(&source)
+                    .await?
+                    .into_iter()
+                    .map(|doc| CloneStep {
+                        parent: None,
+                        node: CloneNode::Doc(doc.slug.into_owned()),
+                    })
+                    .collect();
+
+                (steps, false)
+            }
+        };
+
+        let repo = Repo::builder()
+            .name(name.to_string())
+            .description(
+                source_repo
+                    .description
+                    .map(|s| s.into_owned())
+                    .unwrap_or_default(),
+            )
+            .public(source_repo.public)
+            .book_type(source_repo.book_type.try_into()?)
+            .build()
+            .map_err(|e| YuqueError::Internal(e.to_string()))?;
+
+        let repo = self.create_repo_of_user(user, repo).await?.data;
+
+        let namespace = repo.namespace.to_string();
+        let total = steps
+            .iter()
+            .filter(|step| matches!(step.node, CloneNode::Doc(_)))
+            .count();
+        let mut done = 0;
+        let mut copied = Vec::new();
+        let mut failed = Vec::new();
+        // 每一项的父节点下标以及它在新目录中的 uuid
+        let mut placed: Vec<(Option<usize>, Option<String>)> = Vec::with_capacity(steps.len());
+
+        for step in steps {
+            let mut ancestor = step.parent;
+            let mut target = None;
+
+            while let Some(index) = ancestor {
+                let (parent, uuid) = &placed[index];
+
+                if uuid.is_some() {
+                    target = uuid.clone();
+                    break;
+                }
+
+                ancestor = *parent;
+            }
+
+            let uuid = match step.node {
+                CloneNode::Doc(slug) => {
+                    let result = async {
+                        let doc: Doc = docs
+                            .get_with_repo_ns(&source, &slug, Some(&[("raw", "1")]))
+                            .await?
+                            .data
+                            .try_into()?;
+
+                        let doc = docs.create_with_repo(namespace.as_str(), doc).await?.data;
+
+                        if !rebuild_toc {
+                            return Ok(None);
+                        }
+
+                        self.append_toc_node(&namespace, target, None, Some(doc.id))
+                            .await
+                    }
+                    .await;
+
+                    done += 1;
+                    on_progress(CloneProgress { done, total });
+
+                    match result {
+                        Ok(uuid) => {
+                            copied.push(slug);
+                            uuid
+                        }
+                        Err(e) => {
+                            failed.push((slug, e));
+                            None
+                        }
+                    }
+                }
+                CloneNode::Title(title) => {
+                    match self
+                        .append_toc_node(&namespace, target, Some(title.clone()), None)
+                        .await
+                    {
+                        Ok(uuid) => uuid,
+                        Err(e) => {
+                            failed.push((title, e));
+                            None
+                        }
+                    }
+                }
+            };
+
+            placed.push((step.parent, uuid));
+        }
+
+        Ok(CloneRepoReport {
+            repo,
+            copied,
+            failed,
+        })
+    }
+
+    /// 在 `target`（为 `None` 时是根节点）下追加一个文档或分组，返回新节点的 uuid
+    #[cfg(feature = "toc")]
+    async fn append_toc_node(
+        &self,
+        namespace: &str,
+        target: Option<String>,
+        title: Option<String>,
+        doc_id: Option<i32>,
+    ) -> Result<Option<String>, YuqueError> {
+        let action = TocAction::AppendNode {
+            action_mode: TocActionMode::Child,
+            target_uuid: target.clone(),
+            node_type: match doc_id {
+                Some(_) => TocNodeType::Doc,
+                None => TocNodeType::Title,
+            },
+            title,
+            url: None,
+            doc_ids: doc_id.into_iter().collect(),
+        };
+
+        let toc = self.update_toc(namespace, action).await?.data;
+
+        Ok(last_child_uuid(&Toc::into_tree(toc), target.as_deref()))
+    }
+
+    #[cfg(not(feature = "toc"))]
+    async fn append_toc_node(
+        &self,
+        _namespace: &str,
+        _target: Option<String>,
+        _title: Option<String>,
+        _doc_id: Option<i32>,
+    ) -> Result<Option<String>, YuqueError> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::error::Error;
+
+    use serde_json::json;
+
+    #[cfg(feature = "toc")]
+    use crate::Toc;
+    use crate::{
+        CreateRepoType, QueryParams, Repo, RepoDetail, RepoListItem, RepoListQuery, RepoType,
+        RepoTypeFilter, UpdateRepo, UserDetail, Visibility, YuqueResponse,
+    };
+
+    pub(crate) fn repo_detail_fixture() -> serde_json::Value {
+        json!({
+            "id": 2048,
+            "type": "Book",
+            "slug": "sdk-test",
+            "name": "SDK Test",
+            "namespace": "lzzzt/sdk-test",
+            "user_id": 4096,
+            "user": {
+                "id": 4096,
+                "type": "User",
+                "login": "lzzzt",
+                "name": "Lzzzt",
+                "avatar_url": "https://cdn.nlark.com/yuque/avatar.png",
+                "created_at": "2023-03-01T08:00:00.000Z",
+                "updated_at": "2023-03-01T08:00:00.000Z"
+            },
+            "description": null,
+            "toc_yml": "- type: META\n  count: 1\n  tail_type: DOC\n  base_version_id: 1\n  published: true\n  max_level: 1\n  last_updated_at: '2023-03-01T08:00:00.000Z'\n  version_id: 2\n- type: DOC\n  title: Create By SDK\n  uuid: a\n  url: create-by-sdk\n  prev_uuid: ''\n  sibling_uuid: ''\n  child_uuid: ''\n  parent_uuid: ''\n  doc_id: 1024\n  level: 0\n  id: 1024\n  open_window: 1\n  visible: 1\n",
+            "creator_id": 4096,
+            "public": 1,
+            "items_count": 1,
+            "likes_count": 0,
+            "watches_count": 1,
+            "created_at": "2023-03-01T08:00:00.000Z",
+            "updated_at": "2023-03-01T08:00:00.000Z"
+        })
+    }
+
+    #[test]
+    fn should_convert_repo_list_query() -> Result<(), Box<dyn Error>> {
+        assert!(QueryParams::from(RepoListQuery::new()).is_empty());
+        assert_eq!(
+            QueryParams::from(RepoListQuery::new().repo_type(RepoTypeFilter::All)),
+            QueryParams::from(&[("type", "all")])
+        );
+        assert_eq!(serde_json::to_value(RepoTypeFilter::All)?, json!("all"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_repo_types_in_list() -> Result<(), Box<dyn Error>> {
+        let mut design = repo_detail_fixture();
+        design["type"] = json!("Design");
+
+        let mut sheet = repo_detail_fixture();
+        sheet["type"] = json!("Sheet");
+
+        let list: YuqueResponse<Vec<RepoListItem>> =
+            serde_json::from_value(json!({ "data": [repo_detail_fixture(), design, sheet] }))?;
+
+        assert!(matches!(list[0].book_type, RepoType::Book));
+        assert!(matches!(list[1].book_type, RepoType::Design));
+        assert!(matches!(list[2].book_type, RepoType::Other));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_create_book_or_design_repos() -> Result<(), Box<dyn Error>> {
+        let mut design = repo_detail_fixture();
+        design["type"] = json!("Design");
+
+        let mut sheet = repo_detail_fixture();
+        sheet["type"] = json!("Sheet");
+
+        let repo = Repo::try_from(serde_json::from_value::<RepoDetail>(design)?)?;
+        assert_eq!(repo.book_type, CreateRepoType::Design);
+        assert_eq!(RepoType::from(repo.book_type), RepoType::Design);
+
+        assert!(matches!(
+            Repo::try_from(serde_json::from_value::<RepoDetail>(sheet)?),
+            Err(crate::YuqueError::InvalidParams { .. })
+        ));
+        assert!(serde_json::from_value::<Repo>(
+            json!({ "name": "repo", "slug": "repo", "public": 1, "book_type": "all" })
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_count_repos_from_meta() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::Yuque;
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        runtime.block_on(async {
+            let server = MockServer::start().await;
+
+            for (url, total) in [
+                ("/api/v2/users/lzzzt/repos", 42),
+                ("/api/v2/groups/team/repos", 7),
+            ] {
+                Mock::given(method("GET"))
+                    .and(path(url))
+                    .and(query_param("limit", "1"))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "data": [repo_detail_fixture()],
+                        "meta": { "total": total }
+                    })))
+                    .mount(&server)
+                    .await;
+            }
+
+            let repos = Yuque::builder()
+                .token("token".into())
+                .host(server.uri())
+                .build()?
+                .repos();
+
+            assert_eq!(repos.count_repo_of_user("lzzzt").await?, Some(42));
+            assert_eq!(repos.count_repo_of_group("team").await?, Some(7));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn should_display_repo_list_item() -> Result<(), Box<dyn Error>> {
+        let mut repo: RepoListItem = serde_json::from_value(repo_detail_fixture())?;
+        assert_eq!(repo.to_string(), "lzzzt/sdk-test — SDK Test");
+
+        repo.name = "".into();
+        assert_eq!(repo.to_string(), "lzzzt/sdk-test");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_send_empty_fields_on_update() -> Result<(), Box<dyn Error>> {
+        let repo = Repo::builder()
+            .name("SDK Test".into())
+            .slug("sdk-test".into())
+            .build()?;
+
+        assert!(serde_json::to_value(&repo)?.get("description").is_none());
+
+        let update = serde_json::to_value(UpdateRepo::from(repo))?;
+        assert_eq!(
+            update,
+            json!({ "name": "SDK Test", "slug": "sdk-test", "public": 1 })
+        );
+
+        let update = UpdateRepo::builder().public(Visibility::Private).build()?;
+        assert_eq!(serde_json::to_value(update)?, json!({ "public": 0 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_map_visibility() -> Result<(), Box<dyn Error>> {
+        let mut fixture = repo_detail_fixture();
+
+        fixture["public"] = json!(2);
+        let repo: RepoDetail = serde_json::from_value(fixture.clone())?;
+        assert_eq!(repo.public, Visibility::MembersOnly);
+        assert_eq!(serde_json::to_value(&repo)?["public"], 2);
+
+        fixture["public"] = json!(5);
+        let err = serde_json::from_value::<RepoDetail>(fixture).unwrap_err();
+        assert!(err.to_string().contains("invalid visibility 5"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_parse_toc() -> Result<(), Box<dyn Error>> {
+        let repo: RepoDetail = serde_json::from_value(repo_detail_fixture())?;
+
+        let toc = repo.toc.unwrap();
+
+        assert_eq!(toc.len(), 2);
+        assert!(matches!(&toc[1], Toc::Doc(doc) if doc.url == "create-by-sdk"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_round_trip_toc_yml() -> Result<(), Box<dyn Error>> {
+        let fixture = repo_detail_fixture();
+        let toc_yml = fixture["toc_yml"].as_str().unwrap();
+
+        let toc: Vec<Toc> = serde_yaml::from_str(toc_yml)?;
+        let serialized = serde_yaml::to_string(&toc)?;
+        let reparsed: Vec<Toc> = serde_yaml::from_str(&serialized)?;
+
+        assert_eq!(serde_yaml::to_string(&reparsed)?, serialized);
+        assert!(matches!(&reparsed[0], Toc::Meta(meta) if meta.count == 1 && meta.version_id == 2));
+        assert!(matches!(&reparsed[1], Toc::Doc(doc) if doc.url == "create-by-sdk"));
+
+        let repo: RepoDetail = serde_json::from_value(fixture)?;
+        assert_eq!(serde_yaml::to_string(&repo.toc)?, serialized);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_parse_toc_response() -> Result<(), Box<dyn Error>> {
+        let response: YuqueResponse<Vec<Toc>> = serde_json::from_value(json!({
+            "data": [
+                {
+                    "type": "TITLE",
+                    "title": "Chapter",
+                    "uuid": "a",
+                    "url": "",
+                    "prev_uuid": "",
+                    "sibling_uuid": "",
+                    "child_uuid": "b",
+                    "parent_uuid": "",
+                    "doc_id": "",
+                    "level": 0,
+                    "id": "",
+                    "open_window": 1,
+                    "visible": 1,
+                    "depth": 1
+                },
+                {
+                    "type": "DOC",
+                    "title": "Create By SDK",
+                    "uuid": "b",
+                    "url": "create-by-sdk",
+                    "prev_uuid": "a",
+                    "sibling_uuid": "",
+                    "child_uuid": "",
+                    "parent_uuid": "a",
+                    "doc_id": 1024,
+                    "level": 1,
+                    "id": 1024,
+                    "open_window": 1,
+                    "visible": 1,
+                    "depth": 2,
+                    "slug": "create-by-sdk"
+                }
+            ]
+        }))?;
+
+        let tree = Toc::into_tree(response.data);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].item.title(), Some("Chapter"));
+        assert!(matches!(&tree[0].children[0].item, Toc::Doc(doc) if doc.doc_id == 1024));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_not_fail_on_truncated_toc() -> Result<(), Box<dyn Error>> {
+        let mut fixture = repo_detail_fixture();
+        fixture["toc_yml"] =
+            "- type: META\n  count: 1\n  tail_type: DOC\n- type: DOC\n  title: [".into();
+
+        let repo: RepoDetail = serde_json::from_value(fixture)?;
+
+        assert!(repo.toc.is_none());
+        assert_eq!(repo.namespace, "lzzzt/sdk-test");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_parse_repo_without_toc() -> Result<(), Box<dyn Error>> {
+        let mut fixture = repo_detail_fixture();
+        fixture["toc_yml"] = serde_json::Value::Null;
+
+        let repo: RepoDetail = serde_json::from_value(fixture.clone())?;
+        assert!(repo.toc.is_none());
+
+        fixture.as_object_mut().unwrap().remove("toc_yml");
+
+        let repo: RepoDetail = serde_json::from_value(fixture)?;
+        assert!(repo.toc.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_default_omitted_counts() -> Result<(), Box<dyn Error>> {
+        let mut fixture = repo_detail_fixture();
+        let repo_fields = fixture.as_object_mut().unwrap();
+        repo_fields.remove("items_count");
+        repo_fields.remove("likes_count");
+
+        let repo: RepoDetail = serde_json::from_value(fixture)?;
+
+        assert_eq!(repo.items_count, 0);
+        assert_eq!(repo.likes_count, 0);
+        assert_eq!(repo.watches_count, 1);
+
+        let user: UserDetail = serde_json::from_value(json!({
+            "id": 4096,
+            "space_id": 0,
+            "account_id": 4096,
+            "type": "User",
+            "login": "lzzzt",
+            "name": "Lzzzt",
+            "avatar_url": "https://cdn.nlark.com/yuque/avatar.png",
+            "public_books_count": 2,
+            "created_at": "2023-03-01T08:00:00.000Z",
+            "updated_at": "2023-03-01T08:00:00.000Z"
+        }))?;
+
+        assert_eq!(user.books_count, 0);
+        assert_eq!(user.public_books_count, 2);
+        assert_eq!(user.members_count, 0);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toc")]
+    #[test]
+    fn should_rebuild_toc_when_cloning() -> Result<(), Box<dyn Error>> {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::{docs::test::doc_detail_fixture, Yuque};
+
+        let mut source = repo_detail_fixture();
+        source["toc_yml"] = json!(
+            "- type: TITLE\n  title: Group\n  uuid: g\n  url: ''\n  prev_uuid: ''\n  sibling_uuid: b\n  child_uuid: a\n  parent_uuid: ''\n  doc_id: ''\n  level: 0\n  id: ''\n  open_window: 1\n  visible: 1\n\
+             - type: DOC\n  title: First\n  uuid: a\n  url: first\n  prev_uuid: g\n  sibling_uuid: ''\n  child_uuid: ''\n  parent_uuid: g\n  doc_id: 1\n  level: 1\n  id: 1\n  open_window: 1\n  visible: 1\n\
+             - type: DOC\n  title: Second\n  uuid: b\n  url: second\n  prev_uuid: g\n  sibling_uuid: ''\n  child_uuid: ''\n  parent_uuid: ''\n  doc_id: 2\n  level: 0\n  id: 2\n  open_window: 1\n  visible: 1\n"
+        );
+
+        let mut copy = repo_detail_fixture();
+        copy["namespace"] = json!("lzzzt/copy");
+
+        let toc = json!([{
+            "type": "TITLE",
+            "title": "Group",
+            "uuid": "new-group",
+            "url": "",
+            "prev_uuid": "",
+            "sibling_uuid": "",
+            "child_uuid": "",
+            "parent_uuid": "",
+            "doc_id": "",
+            "level": 0,
+            "id": "",
+            "open_window": 1,
+            "visible": 1
+        }]);
+
+        let runtime = tokio::runtime::Runtime::new()?;
+
+        runtime.block_on(async {
+            let server = MockServer::start().await;
+
+            let routes = [
+                ("GET", "/api/v2/repos/lzzzt/sdk-test", json!({ "data": source })),
+                ("POST", "/api/v2/users/lzzzt/repos", json!({ "data": copy })),
+                ("GET", "/api/v2/repos/lzzzt/sdk-test/docs/first", json!({ "data": doc_detail_fixture() })),
+                ("GET", "/api/v2/repos/lzzzt/sdk-test/docs/second", json!({ "data": doc_detail_fixture() })),
+                ("POST", "/api/v2/repos/lzzzt/copy/docs", json!({ "data": doc_detail_fixture() })),
+                ("PUT", "/api/v2/repos/lzzzt/copy/toc", json!({ "data": toc })),
+            ];
+
+            for (verb, url, body) in routes {
+                Mock::given(method(verb))
+                    .and(path(url))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                    .mount(&server)
+                    .await;
+            }
+
+            let repos = Yuque::builder()
+                .token("token".into())
+                .host(server.uri())
+                .build()?
+                .repos();
+
+            let report = repos.clone_repo("lzzzt/sdk-test", "lzzzt", "copy").await?;
+
+            assert_eq!(report.copied, vec!["first", "second"]);
+            assert!(report.failed.is_empty());
+
+            let actions: Vec<serde_json::Value> = server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .iter()
+                .filter(|request| request.method.as_str() == "PUT")
+                .map(|request| request.body_json())
+                .collect::<Result<_, _>>()?;
+
+            assert_eq!(
+                actions,
+                vec![
+                    json!({ "action": "appendNode", "action_mode": "child", "type": "TITLE", "title": "Group" }),
+                    json!({ "action": "appendNode", "action_mode": "child", "target_uuid": "new-group", "type": "DOC", "doc_ids": [1024] }),
+                    json!({ "action": "appendNode", "action_mode": "child", "type": "DOC", "doc_ids": [1024] }),
+                ]
+            );
+
+            Ok(())
+        })
+    }
 }
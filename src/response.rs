@@ -1,30 +1,303 @@
 #![allow(unused)]
 
-use std::{borrow::Cow, slice::Iter, vec::IntoIter};
+use std::{borrow::Cow, collections::HashMap, ops::Deref, slice::Iter, vec::IntoIter};
 
+use chrono::{DateTime, Local, TimeZone};
+use reqwest::header::HeaderMap;
 use serde::Deserialize;
+use serde_json::Value;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct YuqueResponse<D> {
     pub data: D,
     pub abilities: Option<Abilities>,
+    pub meta: Option<Meta>,
+}
+
+impl<D> YuqueResponse<D> {
+    /// 服务端返回的总数，没有返回 `meta.total` 的接口为 `None`
+    pub fn total(&self) -> Option<u32> {
+        self.meta.as_ref().and_then(|meta| meta.total)
+    }
 }
 
 impl<D> YuqueResponse<Vec<D>> {
-    pub fn iter(&self) -> Iter<D> {
+    pub fn iter(&self) -> Iter<'_, D> {
         self.data.iter()
     }
 
+    /// 本页的条数
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// 本页是否为空
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 本页的第 `index` 项
+    pub fn get(&self, index: usize) -> Option<&D> {
+        self.data.get(index)
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> IntoIter<D> {
         self.data.into_iter()
     }
 }
 
-#[derive(Deserialize, Debug)]
+/// 列表响应可以像切片一样使用，如 `response[0]`、`&response[1..]`
+impl<D> Deref for YuqueResponse<Vec<D>> {
+    type Target = [D];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+/// 从本地数据构造列表响应，`abilities`、`meta` 为空
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::YuqueResponse;
+///
+/// let response: YuqueResponse<Vec<u32>> = (1..=3).collect();
+///
+/// assert_eq!(response.len(), 3);
+/// assert_eq!(response[0], 1);
+/// assert_eq!(&response[1..], &[2, 3]);
+/// assert!(response.total().is_none());
+/// ```
+impl<D> FromIterator<D> for YuqueResponse<Vec<D>> {
+    fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+        Self {
+            data: iter.into_iter().collect(),
+            abilities: None,
+            meta: None,
+        }
+    }
+}
+
+/// 列表响应可以直接用于 `for` 循环
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::YuqueResponse;
+///
+/// let response: YuqueResponse<Vec<u32>> = serde_json::from_str(r#"{"data":[1,2,3]}"#).unwrap();
+///
+/// for id in &response {
+///     assert!(*id > 0);
+/// }
+///
+/// let mut sum = 0;
+///
+/// for id in response {
+///     sum += id;
+/// }
+///
+/// assert_eq!(sum, 6);
+/// ```
+impl<D> IntoIterator for YuqueResponse<Vec<D>> {
+    type Item = D;
+    type IntoIter = IntoIter<D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'r, D> IntoIterator for &'r YuqueResponse<Vec<D>> {
+    type Item = &'r D;
+    type IntoIter = Iter<'r, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// 当前用户对资源的权限
+///
+/// # Fields
+/// * `update: bool` - 是否可以修改
+/// * `destroy: bool` - 是否可以删除
+/// * `read: bool` - 是否可以阅读
+/// * `others: HashMap<String, Value>` - 其余的权限，如仓库的 `create_doc`，也可能是嵌套的对象，如 `{"doc": {"create": true}}`
+///
+/// 服务端没有返回的权限都视为 `false`
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 pub struct Abilities {
-    update: bool,
-    destroy: bool,
+    #[serde(default)]
+    pub update: bool,
+    #[serde(default)]
+    pub destroy: bool,
+    #[serde(default)]
+    pub read: bool,
+    #[serde(flatten)]
+    pub others: HashMap<String, Value>,
+}
+
+impl Abilities {
+    /// 查询权限，包括 `others` 中的权限，没有返回或不是布尔值的权限为 `false`
+    pub fn can(&self, ability: &str) -> bool {
+        match ability {
+            "update" => self.update,
+            "destroy" => self.destroy,
+            "read" => self.read,
+            other => self
+                .others
+                .get(other)
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// total - 列表的总数，分页接口才会返回
+#[derive(Deserialize, Debug, Clone)]
+pub struct Meta {
+    pub total: Option<u32>,
+}
+
+/// 响应头中的限流信息
+///
+/// # Fields
+/// * `limit: Option<u32>` - 时间窗口内允许的请求数，对应 `X-RateLimit-Limit`
+/// * `remaining: Option<u32>` - 剩余的请求数，对应 `X-RateLimit-Remaining`
+/// * `reset: Option<DateTime<Local>>` - 额度重置的时间，对应 `X-RateLimit-Reset`（Unix 时间戳，秒）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    pub reset: Option<DateTime<Local>>,
 }
 
+impl RateLimit {
+    /// 从响应头中解析限流信息，没有任何限流相关的响应头时为 `None`
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
 
+        let rate_limit = RateLimit {
+            limit: header(headers, "X-RateLimit-Limit"),
+            remaining: header(headers, "X-RateLimit-Remaining"),
+            reset: header(headers, "X-RateLimit-Reset")
+                .and_then(|reset: i64| Local.timestamp_opt(reset, 0).single()),
+        };
+
+        (rate_limit.limit.is_some() || rate_limit.remaining.is_some() || rate_limit.reset.is_some())
+            .then_some(rate_limit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Local, TimeZone};
+    use reqwest::header::HeaderMap;
+    use serde_json::json;
+
+    use super::{Abilities, RateLimit, YuqueResponse};
+
+    #[test]
+    fn should_read_total_from_meta() {
+        let response: YuqueResponse<Vec<u32>> = serde_json::from_value(json!({
+            "data": [1, 2],
+            "meta": { "total": 42 }
+        }))
+        .unwrap();
+
+        assert_eq!(response.total(), Some(42));
+
+        let response: YuqueResponse<Vec<u32>> =
+            serde_json::from_value(json!({ "data": [1, 2] })).unwrap();
+
+        assert_eq!(response.total(), None);
+    }
+
+    #[test]
+    fn should_read_len_and_items_of_list() {
+        let response: YuqueResponse<Vec<u32>> =
+            serde_json::from_value(json!({ "data": [1, 2, 3] })).unwrap();
+
+        assert_eq!(response.len(), 3);
+        assert!(!response.is_empty());
+        assert_eq!(response.get(1), Some(&2));
+        assert_eq!(response.get(3), None);
+
+        let empty: YuqueResponse<Vec<u32>> = serde_json::from_value(json!({ "data": [] })).unwrap();
+
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.get(0), None);
+    }
+
+    #[test]
+    fn should_deref_list_to_slice() {
+        let response: YuqueResponse<Vec<u32>> =
+            serde_json::from_value(json!({ "data": [1, 2, 3] })).unwrap();
+
+        assert_eq!(response[0], 1);
+        assert_eq!(&response[1..], &[2, 3]);
+        assert_eq!(response.first(), Some(&1));
+        assert!(response.contains(&3));
+    }
+
+    #[test]
+    fn should_expose_abilities() {
+        let response: YuqueResponse<u32> = serde_json::from_value(json!({
+            "data": 1,
+            "abilities": { "update": true, "destroy": false, "read": true, "create_doc": true }
+        }))
+        .unwrap();
+
+        let abilities = response.abilities.unwrap();
+
+        assert!(abilities.update);
+        assert!(!abilities.destroy);
+        assert!(abilities.read);
+        assert!(abilities.can("create_doc"));
+        assert!(!abilities.can("share"));
+
+        let abilities: Abilities = serde_json::from_value(json!({ "update": true })).unwrap();
+
+        assert!(abilities.can("update"));
+        assert!(!abilities.can("read"));
+    }
+
+    #[test]
+    fn should_keep_nested_abilities() {
+        let response: YuqueResponse<u32> = serde_json::from_value(json!({
+            "data": 1,
+            "abilities": { "update": true, "create_doc": true, "doc": { "create": true } }
+        }))
+        .unwrap();
+
+        let abilities = response.abilities.unwrap();
+
+        assert!(abilities.can("update"));
+        assert!(abilities.can("create_doc"));
+        assert!(!abilities.can("doc"));
+        assert_eq!(abilities.others["doc"], json!({ "create": true }));
+    }
+
+    #[test]
+    fn should_parse_rate_limit_headers() {
+        assert_eq!(RateLimit::from_headers(&HeaderMap::new()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "5000".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "4999".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "1677657600".parse().unwrap());
+
+        assert_eq!(
+            RateLimit::from_headers(&headers),
+            Some(RateLimit {
+                limit: Some(5000),
+                remaining: Some(4999),
+                reset: Local.timestamp_opt(1677657600, 0).single(),
+            })
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+use crate::{handle_response, QueryParams, Yuque, YuqueError, YuqueResponse};
+
+/// 搜索结果
+///
+/// # Fields
+/// * `id: i32` - 文档/仓库编号
+/// * `type: Cow<'a, str>` - 类型 [doc - 文档, book - 仓库]
+/// * `title: Cow<'a, str>` - 标题，命中的关键词会用 `<em>` 标出
+/// * `summary: Cow<'a, str>` - 摘要，命中的关键词会用 `<em>` 标出
+/// * `url: Cow<'a, str>` - 访问路径
+/// * `info: Option<Cow<'a, str>>` - 所属的仓库/团队等信息
+#[derive(Deserialize, Debug)]
+pub struct SearchHit<'a> {
+    pub id: i32,
+    #[serde(rename = "type")]
+    pub hit_type: Cow<'a, str>,
+    pub title: Cow<'a, str>,
+    #[serde(default)]
+    pub summary: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    #[serde(default)]
+    pub info: Option<Cow<'a, str>>,
+}
+
+pub struct SearchClient {
+    pub(crate) client: Yuque,
+}
+
+impl SearchClient {
+    /// Search documents
+    /// 搜索文档
+    ///
+    /// # Arguments
+    /// * `query` - 搜索关键词
+    /// * `data` - 额外的查询参数，如 `offset`、`limit`、`scope`（搜索范围，如 `login/book_slug`）
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let search = yuque.search();
+    ///
+    ///     let response = search.docs("yuque", &[("scope", "lzzzt/sdk-test"), ("limit", "10")]).await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn docs(
+        &self,
+        query: &str,
+        data: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<SearchHit<'_>>>, YuqueError> {
+        self.search(query, "doc", data).await
+    }
+
+    /// Search repos
+    /// 搜索仓库
+    ///
+    /// # Arguments
+    /// * `query` - 搜索关键词
+    /// * `data` - 额外的查询参数，如 `offset`、`limit`、`scope`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let search = yuque.search();
+    ///
+    ///     let response = search.repos("sdk", &[]).await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn repos(
+        &self,
+        query: &str,
+        data: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<SearchHit<'_>>>, YuqueError> {
+        self.search(query, "repo", data).await
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        search_type: &str,
+        data: impl Into<QueryParams>,
+    ) -> Result<YuqueResponse<Vec<SearchHit<'_>>>, YuqueError> {
+        let url = "/search".to_string();
+
+        let request = self
+            .client
+            .get(&url)?
+            .query(&[("q", query), ("type", search_type)])
+            .query(&data.into());
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{SearchHit, YuqueResponse};
+
+    #[test]
+    fn should_parse_search_hits() {
+        let response: YuqueResponse<Vec<SearchHit>> = serde_json::from_value(json!({
+            "meta": { "total": 1 },
+            "data": [{
+                "id": 1024,
+                "type": "doc",
+                "title": "Create By <em>SDK</em>",
+                "summary": "This sentence is created by yuque-rust <em>sdk</em>.",
+                "url": "/lzzzt/sdk-test/create-by-sdk",
+                "info": "Lzzzt / SDK Test",
+                "target": { "id": 1024 }
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(response.total(), Some(1));
+        assert_eq!(response.data[0].hit_type, "doc");
+        assert_eq!(response.data[0].url, "/lzzzt/sdk-test/create-by-sdk");
+    }
+}
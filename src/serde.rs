@@ -1,115 +1,194 @@
+use serde::{ser, Serialize, Serializer};
+use serde_json::Value;
+
+/// Serialize the wrapped value with `camelCase` field names
+/// 以 camelCase 字段名序列化内部的值
+///
+/// 请求语雀时仍然使用原本的 snake_case 字段，这里是给非语雀的下游（如 JS 前端）
+/// 准备的另一条序列化路径，嵌套的对象和数组也会一并转换。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{CamelCase, Repo};
+///
+/// let repo = Repo::builder().name("repo".into()).build().unwrap();
+///
+/// let json = serde_json::to_value(CamelCase(&repo)).unwrap();
+///
+/// assert!(json.get("bookType").is_some());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CamelCase<T>(pub T);
+
+impl<T: Serialize> Serialize for CamelCase<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = serde_json::to_value(&self.0).map_err(ser::Error::custom)?;
+
+        camel_case_keys(value).serialize(serializer)
+    }
+}
+
+fn camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (to_camel_case(&key), camel_case_keys(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camel_case_keys).collect()),
+        value => value,
+    }
+}
+
+fn to_camel_case(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper = false;
+
+    for c in key.chars() {
+        if c == '_' && !result.is_empty() {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
 pub(crate) mod time_serde {
-    use chrono::{offset::Local as offset_local, DateTime, Local};
+    use chrono::{DateTime, Local, TimeZone};
     use serde::{
         de::{self, Visitor},
         Deserializer, Serializer,
     };
 
-    #[allow(unused)]
     pub fn serialize<S: Serializer>(
-        time: DateTime<Local>,
+        time: &DateTime<Local>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        serializer.serialize_i64(time.timestamp_millis())
+        serializer.serialize_str(&time.to_rfc3339())
     }
 
+    /// 接受 RFC3339 字符串，或者毫秒级的 Unix 时间戳（整数、浮点数或数字字符串）
     pub fn deserialize<'de, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<DateTime<Local>, D::Error> {
-        let time: String = deserializer.deserialize_string(StrVisitor)?;
-        let time = DateTime::parse_from_rfc3339(&time).map_err(de::Error::custom)?;
-        let now = *Local::now().offset();
-        Ok(DateTime::<offset_local>::from_local(
-            time.naive_local(),
-            now,
-        ))
+        deserializer.deserialize_any(TimeVisitor)
     }
 
-    struct StrVisitor;
+    struct TimeVisitor;
 
-    impl<'de> Visitor<'de> for StrVisitor {
-        type Value = String;
+    impl<'de> Visitor<'de> for TimeVisitor {
+        type Value = DateTime<Local>;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(formatter, "is a string")
+            write!(
+                formatter,
+                "an RFC3339 string or a timestamp in milliseconds"
+            )
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
         where
             E: de::Error,
         {
-            Ok(v.to_string())
+            match DateTime::parse_from_rfc3339(v) {
+                Ok(time) => Ok(time.with_timezone(&Local)),
+                Err(e) => match v.trim().parse::<i64>() {
+                    Ok(millis) => self.visit_i64(millis),
+                    Err(_) => Err(E::custom(e)),
+                },
+            }
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Local
+                .timestamp_millis_opt(v)
+                .single()
+                .ok_or_else(|| E::custom(format!("timestamp {v} is out of range")))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let v = i64::try_from(v)
+                .map_err(|_| E::custom(format!("timestamp {v} is out of range")))?;
+
+            self.visit_i64(v)
         }
 
-        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
         where
             E: de::Error,
         {
-            Ok(v)
+            if !v.is_finite() {
+                return Err(E::custom(format!("timestamp {v} is not a number")));
+            }
+
+            self.visit_i64(v as i64)
         }
     }
 }
 
 pub(crate) mod option_time_serde {
-    #![allow(unused)]
-
-    use chrono::{offset::Local as offset_local, DateTime, Local};
+    use chrono::{DateTime, Local};
     use serde::{
         de::{self, Visitor},
         Deserializer, Serializer,
     };
 
     pub fn serialize<S: Serializer>(
-        time: Option<DateTime<Local>>,
+        time: &Option<DateTime<Local>>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
         if let Some(time) = time {
-            serializer.serialize_i64(time.timestamp_millis())
+            serializer.serialize_str(&time.to_rfc3339())
         } else {
             serializer.serialize_none()
         }
     }
 
+    /// 与 `time_serde` 接受相同的格式，null 或无法解析时为 `None`
     pub fn deserialize<'de, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Option<DateTime<Local>>, D::Error> {
-        let time: Option<String> = deserializer.deserialize_str(StrVisitor).ok().flatten();
-        if let Some(time) = time {
-            let time = DateTime::parse_from_rfc3339(&time).map_err(de::Error::custom)?;
-            let now = *Local::now().offset();
-            Ok(Some(DateTime::<offset_local>::from_local(
-                time.naive_local(),
-                now,
-            )))
-        } else {
-            Ok(None)
-        }
+        Ok(deserializer
+            .deserialize_option(OptionVisitor)
+            .ok()
+            .flatten())
     }
 
-    struct StrVisitor;
+    struct OptionVisitor;
 
-    impl<'de> Visitor<'de> for StrVisitor {
-        type Value = Option<String>;
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<DateTime<Local>>;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(formatter, "is a string")
+            write!(formatter, "an optional time")
         }
 
-        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
         where
-            E: de::Error,
+            D: Deserializer<'de>,
         {
-            Ok(Some(v.to_string()))
+            super::time_serde::deserialize(deserializer).map(Some)
         }
 
-        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        fn visit_none<E>(self) -> Result<Self::Value, E>
         where
             E: de::Error,
         {
-            Ok(Some(v))
+            Ok(None)
         }
 
-        fn visit_none<E>(self) -> Result<Self::Value, E>
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
         where
             E: de::Error,
         {
@@ -121,7 +200,6 @@ pub(crate) mod option_time_serde {
 pub(crate) mod number_to_bool {
     use serde::{de::Error, Deserialize, Deserializer, Serializer};
 
-    #[allow(unused)]
     pub fn serialize<S: Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
         if *value {
             serializer.serialize_u8(1)
@@ -140,88 +218,154 @@ pub(crate) mod number_to_bool {
     }
 }
 
+#[cfg(feature = "toc")]
 pub(crate) mod toc_serde {
-    use serde::{
-        de::{self, Visitor},
-        Deserializer,
-    };
+    use serde::{ser, Deserialize, Deserializer, Serializer};
 
     use crate::Toc;
 
-    // #[allow(unused)]
-    // pub fn serialize<S: Serializer>(value: Option<Toc>, serializer: S) -> Result<S::Ok, S::Error> {
-    //     if let Some(value) = value {
-    //         let Toc { meta, toc } = value;
-
-    //         let meta = vec![meta];
-    //         let meta = to_string(&meta).map_err(|e| serde::ser::Error::custom(e.to_string()))?;
-    //         let toc = to_string(&toc).map_err(|e| serde::ser::Error::custom(e.to_string()))?;
-
-    //         let result = vec![meta, toc].join("\n");
-
-    //         serializer.serialize_str(&result)
-    //     } else {
-    //         serializer.serialize_none()
-    //     }
-    // }
+    /// 写回 `toc_yml` 的 YAML 原文，`None` 写为 null
+    pub fn serialize<S: Serializer>(
+        value: &Option<Vec<Toc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(toc) => {
+                let toc = serde_yaml::to_string(toc).map_err(ser::Error::custom)?;
+                serializer.serialize_str(&toc)
+            }
+            None => serializer.serialize_none(),
+        }
+    }
 
+    /// `toc_yml` 为 null 或者无法解析（比如权限不足时返回的不完整目录）时得到 `None`，
+    /// 不会让整个 `RepoDetail` 反序列化失败。
     pub fn deserialize<'de, 'a, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Option<Vec<Toc<'a>>>, D::Error> {
-        let value: String = deserializer.deserialize_string(StrVisitor)?.unwrap();
+        let value: Option<String> = Option::deserialize(deserializer)?;
 
-        Ok(serde_yaml::from_str(&value).unwrap())
+        Ok(value.and_then(|value| serde_yaml::from_str(&value).ok()))
+    }
+}
 
-        // let meta = value
-        //     .lines()
-        //     .take(9)
-        //     .map(|s| format!("{}\n", s))
-        //     .collect::<String>();
-        // let toc = value
-        //     .lines()
-        //     .skip(9)
-        //     .map(|s| format!("{}\n", s))
-        //     .collect::<String>();
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, Local, TimeZone};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
 
-        // let meta = serde_yaml::from_str::<Vec<TocMeta>>(&meta)
-        //     .map_err(|e| de::Error::custom(e.to_string()))?
-        //     .pop()
-        //     .expect("Can not fine Metadata.");
+    use super::{number_to_bool, option_time_serde, time_serde, CamelCase};
+    use crate::{Doc, Repo};
 
-        // let toc = serde_yaml::from_str::<Vec<TocDocItem>>(&toc)
-        //     .map_err(|e| de::Error::custom(e.to_string()))?;
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Times {
+        #[serde(with = "time_serde")]
+        time: DateTime<Local>,
+        #[serde(default, with = "option_time_serde")]
+        optional: Option<DateTime<Local>>,
+    }
 
-        // Ok(Some(Toc { meta, toc }))
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Flag {
+        #[serde(with = "number_to_bool")]
+        public: bool,
     }
 
-    struct StrVisitor;
+    #[test]
+    fn should_parse_rfc3339_and_epoch_millis() {
+        let expected = Local.timestamp_millis_opt(1677657600000).unwrap();
+
+        for time in [
+            json!("2023-03-01T08:00:00.000Z"),
+            json!(1677657600000_u64),
+            json!(1677657600000.0),
+            json!("1677657600000"),
+        ] {
+            let times: Times =
+                serde_json::from_value(json!({ "time": time, "optional": time })).unwrap();
+
+            assert_eq!(times.time, expected);
+            assert_eq!(times.optional, Some(expected));
+        }
 
-    impl<'de> Visitor<'de> for StrVisitor {
-        type Value = Option<String>;
+        let times: Times =
+            serde_json::from_value(json!({ "time": -1000, "optional": null })).unwrap();
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(formatter, "is a string")
-        }
+        assert_eq!(times.time, Local.timestamp_millis_opt(-1000).unwrap());
+        assert_eq!(times.optional, None);
 
-        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            Ok(Some(v.to_string()))
-        }
+        assert!(serde_json::from_value::<Times>(json!({ "time": "yesterday" })).is_err());
+    }
 
-        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            Ok(Some(v))
+    #[test]
+    fn should_round_trip_times_and_flags() {
+        let time = Local.timestamp_millis_opt(1677657600123).unwrap();
+
+        for optional in [Some(time), None] {
+            let times = Times { time, optional };
+
+            let value = serde_json::to_value(&times).unwrap();
+            assert_eq!(value["time"], json!(time.to_rfc3339()));
+
+            assert_eq!(serde_json::from_value::<Times>(value).unwrap(), times);
         }
 
-        fn visit_none<E>(self) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            Ok(None)
+        for public in [true, false] {
+            let value = serde_json::to_value(Flag { public }).unwrap();
+            assert_eq!(value, json!({ "public": u8::from(public) }));
+
+            assert_eq!(
+                serde_json::from_value::<Flag>(value).unwrap(),
+                Flag { public }
+            );
         }
     }
+
+    #[test]
+    fn should_serialize_fields_as_camel_case() {
+        let repo = Repo::builder()
+            .name("repo".into())
+            .slug("repo".into())
+            .description("desc".into())
+            .build()
+            .unwrap();
+
+        let value = serde_json::to_value(CamelCase(&repo)).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "name": "repo",
+                "slug": "repo",
+                "description": "desc",
+                "public": 1,
+                "bookType": "Book",
+            })
+        );
+    }
+
+    #[test]
+    fn should_keep_wire_format_snake_case() {
+        let repo = Repo::builder().name("repo".into()).build().unwrap();
+
+        let value = serde_json::to_value(&repo).unwrap();
+
+        assert!(value.get("book_type").is_some());
+        assert!(value.get("bookType").is_none());
+    }
+
+    #[test]
+    fn should_convert_nested_values() {
+        let doc = Doc::builder().title("title".into()).build().unwrap();
+
+        let value = serde_json::to_value(CamelCase(json!({
+            "doc_list": [doc],
+            "_private_key": { "inner_key": 1 },
+        })))
+        .unwrap();
+
+        assert_eq!(value["docList"][0]["title"], "title");
+        assert_eq!(value["_privateKey"]["innerKey"], 1);
+    }
 }
@@ -0,0 +1,133 @@
+use rand::{distributions::Alphanumeric, rngs::ThreadRng, Rng};
+
+/// `Doc::builder()` 默认生成的 slug 长度
+pub(crate) const DOC_SLUG_LEN: usize = 16;
+/// `Repo::builder()` 默认生成的 slug 长度
+pub(crate) const REPO_SLUG_LEN: usize = 6;
+
+/// slug 的生成策略
+///
+/// `Doc::builder()`、`Repo::builder()` 没有设置 slug 时使用 [`RandomSlug`] 生成随机的 slug，
+/// 通过 `DocBuilder::slug_with`、`RepoBuilder::slug_with` 可以换成其他策略，
+/// 比如在测试中使用固定种子的随机数，或者统一加上前缀。
+///
+/// 闭包 `FnMut(usize) -> String` 也实现了这个 trait。
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::Doc;
+///
+/// let mut prefixed = |len: usize| format!("sync-{}", "x".repeat(len));
+///
+/// let doc = Doc::builder()
+///     .title("title".into())
+///     .slug_with(&mut prefixed)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(doc.slug, format!("sync-{}", "x".repeat(16)));
+/// ```
+pub trait SlugGenerator {
+    /// 生成一个 slug，`len` 是默认策略使用的长度，自定义的策略可以忽略它
+    fn generate(&mut self, len: usize) -> String;
+}
+
+impl<F: FnMut(usize) -> String> SlugGenerator for F {
+    fn generate(&mut self, len: usize) -> String {
+        self(len)
+    }
+}
+
+/// 由字母和数字组成的随机 slug，默认的生成策略
+///
+/// 默认使用 `thread_rng`，可以通过 `from_rng` 传入固定种子的随机数生成器得到可复现的结果。
+///
+/// # Example
+/// ```rust
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use yuque_rust::{RandomSlug, SlugGenerator};
+///
+/// let mut a = RandomSlug::from_rng(StdRng::seed_from_u64(42));
+/// let mut b = RandomSlug::from_rng(StdRng::seed_from_u64(42));
+///
+/// assert_eq!(a.generate(8), b.generate(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomSlug<R = ThreadRng> {
+    rng: R,
+}
+
+impl RandomSlug {
+    /// 使用 `thread_rng` 生成
+    pub fn new() -> Self {
+        Self::from_rng(rand::thread_rng())
+    }
+}
+
+impl Default for RandomSlug {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Rng> RandomSlug<R> {
+    /// 使用给定的随机数生成器
+    pub fn from_rng(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> SlugGenerator for RandomSlug<R> {
+    fn generate(&mut self, len: usize) -> String {
+        (&mut self.rng)
+            .sample_iter(&Alphanumeric)
+            .take(len)
+            .map(char::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{RandomSlug, SlugGenerator};
+    use crate::{Doc, Repo};
+
+    #[test]
+    fn should_generate_reproducible_slugs() -> Result<(), Box<dyn std::error::Error>> {
+        let mut seeded = RandomSlug::from_rng(StdRng::seed_from_u64(7));
+        let expected = RandomSlug::from_rng(StdRng::seed_from_u64(7)).generate(16);
+
+        let doc = Doc::builder()
+            .title("title".into())
+            .slug_with(&mut seeded)
+            .build()?;
+        assert_eq!(doc.slug, expected);
+
+        let mut counter = 0;
+        let mut sequential = |_| {
+            counter += 1;
+            format!("repo-{counter}")
+        };
+
+        let first = Repo::builder()
+            .name("a".into())
+            .slug_with(&mut sequential)
+            .build()?;
+        let second = Repo::builder()
+            .name("b".into())
+            .slug_with(&mut sequential)
+            .build()?;
+        assert_eq!(
+            (first.slug.as_str(), second.slug.as_str()),
+            ("repo-1", "repo-2")
+        );
+
+        let slug = RandomSlug::new().generate(6);
+        assert_eq!(slug.len(), 6);
+        assert!(slug.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{handle_response, RepoRef, Yuque, YuqueError, YuqueResponse};
+
+/// 仓库的统计汇总
+///
+/// # Fields
+/// * `reads: i32` - 阅读量，对应 `read_count`
+/// * `likes: i32` - 点赞数，对应 `like_count`
+/// * `docs_count: i32` - 文档数量，对应 `doc_count`
+/// * `watches: i32` - 订阅数，对应 `watch_count`
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RepoStats {
+    #[serde(default, rename = "read_count")]
+    pub reads: i32,
+    #[serde(default, rename = "like_count")]
+    pub likes: i32,
+    #[serde(default, rename = "doc_count")]
+    pub docs_count: i32,
+    #[serde(default, rename = "watch_count")]
+    pub watches: i32,
+}
+
+/// 团队的统计汇总
+///
+/// # Fields
+/// * `reads: i32` - 阅读量，对应 `read_count`
+/// * `likes: i32` - 点赞数，对应 `like_count`
+/// * `docs_count: i32` - 文档数量，对应 `doc_count`
+/// * `repos_count: i32` - 仓库数量，对应 `book_count`
+/// * `members_count: i32` - 成员数量，对应 `member_count`
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GroupStats {
+    #[serde(default, rename = "read_count")]
+    pub reads: i32,
+    #[serde(default, rename = "like_count")]
+    pub likes: i32,
+    #[serde(default, rename = "doc_count")]
+    pub docs_count: i32,
+    #[serde(default, rename = "book_count")]
+    pub repos_count: i32,
+    #[serde(default, rename = "member_count")]
+    pub members_count: i32,
+}
+
+pub struct StatisticsClient {
+    pub(crate) client: Yuque,
+}
+
+impl StatisticsClient {
+    /// Get the statistics of a repo
+    /// 获取仓库的统计汇总
+    ///
+    /// # Arguments
+    /// * `repo: impl Into<RepoRef>` - 仓库的命名空间/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let statistics = yuque.statistics();
+    ///
+    ///     let stats = statistics.repo("lzzzt/sdk-test").await?.data;
+    ///
+    ///     println!("{} reads in {} docs", stats.reads, stats.docs_count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn repo(
+        &self,
+        repo: impl Into<RepoRef>,
+    ) -> Result<YuqueResponse<RepoStats>, YuqueError> {
+        let repo: RepoRef = repo.into();
+        let url = format!("{}/statistic", repo.path());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+
+    /// Get the statistics of a group
+    /// 获取团队的统计汇总
+    ///
+    /// # Arguments
+    /// * `group` - 团队的 login/id
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let statistics = yuque.statistics();
+    ///
+    ///     let stats = statistics.group("your group").await?.data;
+    ///
+    ///     println!("{} members", stats.members_count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn group(
+        &self,
+        group: impl ToString,
+    ) -> Result<YuqueResponse<GroupStats>, YuqueError> {
+        let url = format!("/groups/{}/statistic", group.to_string());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{GroupStats, RepoStats, YuqueResponse};
+
+    #[test]
+    fn should_parse_stats_with_missing_counts() {
+        let response: YuqueResponse<RepoStats> = serde_json::from_value(json!({
+            "data": { "read_count": 42, "like_count": 3, "doc_count": 7 }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.data,
+            RepoStats {
+                reads: 42,
+                likes: 3,
+                docs_count: 7,
+                watches: 0,
+            }
+        );
+
+        let response: YuqueResponse<GroupStats> = serde_json::from_value(json!({
+            "data": { "book_count": 2, "member_count": 5 }
+        }))
+        .unwrap();
+
+        assert_eq!(response.data.repos_count, 2);
+        assert_eq!(response.data.members_count, 5);
+        assert_eq!(response.data.reads, 0);
+    }
+}
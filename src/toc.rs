@@ -0,0 +1,465 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{option_time_serde, YuqueError};
+
+/// 目录中的一项，对应 `toc_yml` 列表中的一个元素，按 `type` 区分
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum Toc<'a> {
+    #[serde(rename = "META")]
+    Meta(TocMeta<'a>),
+    #[serde(rename = "DOC")]
+    Doc(TocDocItem<'a>),
+    #[serde(rename = "TITLE")]
+    Title(TocTitleItem<'a>),
+}
+
+/// 目录的元信息，即 `toc_yml` 中 `type: META` 的一项
+///
+/// 语雀增减元信息字段时不应影响目录的解析：未知字段会被忽略，缺少的字段取默认值。
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TocMeta<'a> {
+    #[serde(default)]
+    pub count: u32,
+    // pub display_level: Cow<'a, str>,
+    #[serde(default)]
+    pub tail_type: Cow<'a, str>,
+    #[serde(default)]
+    pub base_version_id: u32,
+    #[serde(default)]
+    pub published: bool,
+    #[serde(default)]
+    pub max_level: u32,
+    #[serde(default, with = "option_time_serde")]
+    pub last_updated_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub version_id: u32,
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq, Eq)]
+#[allow(unused)]
+pub struct TocDocItem<'a> {
+    pub title: Cow<'a, str>,
+    pub uuid: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    pub prev_uuid: Cow<'a, str>,
+    pub sibling_uuid: Cow<'a, str>,
+    pub child_uuid: Cow<'a, str>,
+    pub parent_uuid: Cow<'a, str>,
+    pub doc_id: u32,
+    pub level: u32,
+    pub id: u32,
+    pub open_window: u32,
+    pub visible: u32,
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TocTitleItem<'a> {
+    pub title: Cow<'a, str>,
+    pub uuid: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    pub prev_uuid: Cow<'a, str>,
+    pub sibling_uuid: Cow<'a, str>,
+    pub child_uuid: Cow<'a, str>,
+    pub parent_uuid: Cow<'a, str>,
+    pub doc_id: Cow<'a, str>,
+    pub level: u32,
+    pub id: Cow<'a, str>,
+    pub open_window: u32,
+    pub visible: u32,
+}
+
+impl<'a> Toc<'a> {
+    /// Parse a `toc_yml` string
+    /// 解析 `toc_yml` 原文
+    ///
+    /// 与 `RepoDetail::toc` 不同，解析失败时返回错误而不是 `None`，方便排查目录格式的问题。
+    ///
+    /// # Example
+    /// ```rust
+    /// use yuque_rust::Toc;
+    ///
+    /// let toc = Toc::parse_yml("- type: META\n  count: 0\n  version_id: 1\n").unwrap();
+    ///
+    /// assert!(matches!(&toc[0], Toc::Meta(meta) if meta.version_id == 1));
+    /// assert!(Toc::parse_yml("- type: DOC\n  title: [").is_err());
+    /// ```
+    pub fn parse_yml(toc_yml: &str) -> Result<Vec<Toc<'a>>, YuqueError> {
+        serde_yaml::from_str(toc_yml)
+            .map_err(|e| YuqueError::Internal(format!("invalid toc_yml: {e}")))
+    }
+
+    /// 节点的 uuid，`META` 没有 uuid
+    pub fn uuid(&self) -> Option<&str> {
+        match self {
+            Toc::Meta(_) => None,
+            Toc::Doc(doc) => Some(&doc.uuid),
+            Toc::Title(title) => Some(&title.uuid),
+        }
+    }
+
+    /// 节点的标题，`META` 没有标题
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            Toc::Meta(_) => None,
+            Toc::Doc(doc) => Some(&doc.title),
+            Toc::Title(title) => Some(&title.title),
+        }
+    }
+
+    /// 节点的层级，从 0 开始
+    pub fn level(&self) -> Option<u32> {
+        match self {
+            Toc::Meta(_) => None,
+            Toc::Doc(doc) => Some(doc.level),
+            Toc::Title(title) => Some(title.level),
+        }
+    }
+
+    fn links(&self) -> Option<TocLinks> {
+        let (parent, sibling, child) = match self {
+            Toc::Meta(_) => return None,
+            Toc::Doc(doc) => (&doc.parent_uuid, &doc.sibling_uuid, &doc.child_uuid),
+            Toc::Title(title) => (&title.parent_uuid, &title.sibling_uuid, &title.child_uuid),
+        };
+
+        let non_empty = |uuid: &Cow<str>| (!uuid.is_empty()).then(|| uuid.to_string());
+
+        Some(TocLinks {
+            parent: non_empty(parent),
+            sibling: non_empty(sibling),
+            child: non_empty(child),
+        })
+    }
+
+    /// Build the toc tree from the flat toc list
+    /// 将扁平的目录组装成树
+    ///
+    /// 同一层级的节点按语雀的指针顺序排列：父节点的 `child_uuid` 是第一个子节点，
+    /// 之后沿着 `sibling_uuid` 依次往后；顶层从不是任何节点 `sibling_uuid` 的顶层节点开始。
+    /// 指针断开时，没有被串起来的节点按它们在列表中的顺序追加在同级末尾，
+    /// 所以结果总是确定的，也不会丢失节点。`META` 会被忽略。
+    pub fn into_tree(items: Vec<Toc<'a>>) -> Vec<TocNode<'a>> {
+        let mut order = Vec::new();
+        let mut links = HashMap::new();
+        let mut nodes = HashMap::new();
+
+        for item in items {
+            if let (Some(uuid), Some(link)) = (item.uuid().map(str::to_string), item.links()) {
+                order.push(uuid.clone());
+                links.insert(uuid.clone(), link);
+                nodes.insert(uuid, item);
+            }
+        }
+
+        let head = order
+            .iter()
+            .filter(|uuid| links[*uuid].parent.is_none())
+            .find(|uuid| {
+                links
+                    .values()
+                    .all(|link| link.sibling.as_ref() != Some(*uuid))
+            })
+            .cloned();
+
+        let mut builder = TocTreeBuilder {
+            order,
+            links,
+            nodes,
+        };
+
+        builder.children(None, head)
+    }
+}
+
+/// 目录树的节点
+///
+/// * `item` - 目录项
+/// * `children` - 按目录顺序排列的子节点
+#[derive(Debug)]
+pub struct TocNode<'a> {
+    pub item: Toc<'a>,
+    pub children: Vec<TocNode<'a>>,
+}
+
+struct TocLinks {
+    parent: Option<String>,
+    sibling: Option<String>,
+    child: Option<String>,
+}
+
+struct TocTreeBuilder<'a> {
+    order: Vec<String>,
+    links: HashMap<String, TocLinks>,
+    nodes: HashMap<String, Toc<'a>>,
+}
+
+impl<'a> TocTreeBuilder<'a> {
+    fn is_pending_child(&self, uuid: &str, parent: Option<&str>) -> bool {
+        self.nodes.contains_key(uuid) && self.links[uuid].parent.as_deref() == parent
+    }
+
+    fn children(&mut self, parent: Option<&str>, head: Option<String>) -> Vec<TocNode<'a>> {
+        let mut siblings: Vec<String> = Vec::new();
+        let mut current = head;
+
+        while let Some(uuid) = current {
+            if siblings.contains(&uuid) || !self.is_pending_child(&uuid, parent) {
+                break;
+            }
+
+            current = self.links[&uuid].sibling.clone();
+            siblings.push(uuid);
+        }
+
+        let rest: Vec<String> = self
+            .order
+            .iter()
+            .filter(|uuid| !siblings.contains(uuid) && self.is_pending_child(uuid, parent))
+            .cloned()
+            .collect();
+
+        siblings.extend(rest);
+
+        let items: Vec<(String, Toc<'a>)> = siblings
+            .into_iter()
+            .filter_map(|uuid| self.nodes.remove(&uuid).map(|item| (uuid, item)))
+            .collect();
+
+        items
+            .into_iter()
+            .map(|(uuid, item)| {
+                let head = self.links[&uuid].child.clone();
+                let children = self.children(Some(&uuid), head);
+
+                TocNode { item, children }
+            })
+            .collect()
+    }
+}
+
+/// 修改目录的操作，对应 `PUT /repos/:namespace/toc` 的 `action`
+///
+/// * `AppendNode` / `PrependNode` - 在 `target_uuid` 之后/之前插入节点，`action_mode` 为
+///   `Child` 时作为它的子节点插入；`target_uuid` 为空时插入到根节点
+/// * `EditNode` - 修改 `node_uuid` 节点，只提交设置了的字段；设置了 `target_uuid` 时移动节点
+/// * `RemoveNode` - 删除 `node_uuid` 节点，`action_mode` 为 `Child` 时连同子节点一起删除
+///
+/// # Example
+/// ```rust
+/// use yuque_rust::{TocAction, TocActionMode, TocNodeType};
+///
+/// let action = TocAction::AppendNode {
+///     action_mode: TocActionMode::Child,
+///     target_uuid: Some("parent".into()),
+///     node_type: TocNodeType::Doc,
+///     title: None,
+///     url: None,
+///     doc_ids: vec![1024],
+/// };
+///
+/// let json = serde_json::to_value(&action).unwrap();
+///
+/// assert_eq!(json["action"], "appendNode");
+/// assert_eq!(json["type"], "DOC");
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum TocAction {
+    AppendNode {
+        action_mode: TocActionMode,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target_uuid: Option<String>,
+        #[serde(rename = "type")]
+        node_type: TocNodeType,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        doc_ids: Vec<i32>,
+    },
+    PrependNode {
+        action_mode: TocActionMode,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target_uuid: Option<String>,
+        #[serde(rename = "type")]
+        node_type: TocNodeType,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        doc_ids: Vec<i32>,
+    },
+    EditNode {
+        node_uuid: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target_uuid: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        open_window: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        visible: Option<u8>,
+    },
+    RemoveNode {
+        node_uuid: String,
+        action_mode: TocActionMode,
+    },
+}
+
+/// 插入/删除节点时相对于目标节点的位置
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TocActionMode {
+    #[default]
+    Sibling,
+    Child,
+}
+
+/// 新节点的类型
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TocNodeType {
+    Doc,
+    Link,
+    Title,
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{Toc, TocAction, TocActionMode, TocNode};
+
+    const DOC: &str = "- type: DOC\n  title: a\n  uuid: a\n  url: a\n  prev_uuid: ''\n  sibling_uuid: ''\n  child_uuid: ''\n  parent_uuid: ''\n  doc_id: 1\n  level: 0\n  id: 1\n  open_window: 1\n  visible: 1\n";
+
+    #[test]
+    fn should_serialize_toc_actions() {
+        let action = TocAction::EditNode {
+            node_uuid: "a".into(),
+            target_uuid: None,
+            title: Some("renamed".into()),
+            url: None,
+            open_window: None,
+            visible: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            json!({ "action": "editNode", "node_uuid": "a", "title": "renamed" })
+        );
+
+        let action = TocAction::RemoveNode {
+            node_uuid: "a".into(),
+            action_mode: TocActionMode::Child,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            json!({ "action": "removeNode", "node_uuid": "a", "action_mode": "child" })
+        );
+    }
+
+    #[test]
+    fn should_parse_meta_with_extra_fields() {
+        let toc_yml = format!("- type: META\n  count: 1\n  display_level: ''\n  tail_type: DOC\n  base_version_id: 1\n  published: true\n  max_level: 1\n  last_updated_at: '2023-03-01T08:00:00.000Z'\n  version_id: 2\n  new_field: 3\n{DOC}");
+
+        let toc = Toc::parse_yml(&toc_yml).unwrap();
+
+        assert_eq!(toc.len(), 2);
+        assert!(
+            matches!(&toc[0], Toc::Meta(meta) if meta.version_id == 2 && meta.last_updated_at.is_some())
+        );
+    }
+
+    #[test]
+    fn should_parse_meta_with_missing_fields() {
+        let toc_yml = format!("- type: META\n  count: 1\n  version_id: 2\n{DOC}");
+
+        let toc = Toc::parse_yml(&toc_yml).unwrap();
+
+        assert_eq!(toc.len(), 2);
+        assert!(
+            matches!(&toc[0], Toc::Meta(meta) if meta.count == 1 && meta.last_updated_at.is_none())
+        );
+        assert!(matches!(&toc[1], Toc::Doc(doc) if doc.doc_id == 1));
+    }
+
+    fn item(
+        kind: &str,
+        uuid: &str,
+        parent: &str,
+        sibling: &str,
+        child: &str,
+        level: u32,
+    ) -> String {
+        let id = if kind == "TITLE" { "''" } else { "1" };
+
+        format!(
+            "- type: {kind}\n  title: {uuid}\n  uuid: {uuid}\n  url: {uuid}\n  prev_uuid: ''\n  sibling_uuid: '{sibling}'\n  child_uuid: '{child}'\n  parent_uuid: '{parent}'\n  doc_id: {id}\n  level: {level}\n  id: {id}\n  open_window: 1\n  visible: 1\n"
+        )
+    }
+
+    fn titles(nodes: &[TocNode]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| {
+                let title = node.item.title().unwrap().to_string();
+
+                if node.children.is_empty() {
+                    title
+                } else {
+                    format!("{title}({})", titles(&node.children).join(","))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_append_unlinked_items_in_list_order() {
+        let yml = [
+            item("DOC", "a", "", "", "", 0),
+            item("DOC", "b", "", "", "", 0),
+            item("DOC", "b1", "b", "", "", 1),
+        ]
+        .concat();
+
+        let toc: Vec<Toc> = serde_yaml::from_str(&yml).unwrap();
+
+        assert_eq!(
+            titles(&Toc::into_tree(toc)),
+            ["a", "b(b1)"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn should_keep_sibling_order_of_multi_level_toc() {
+        // 列表中的顺序被打乱，树的顺序应当以指针为准
+        let yml = [
+            item("DOC", "c", "", "", "", 0),
+            item("DOC", "a2", "a", "a3", "", 1),
+            item("TITLE", "a", "", "b", "a1", 0),
+            item("DOC", "a1", "a", "a2", "a1x", 1),
+            item("DOC", "b", "", "c", "", 0),
+            item("DOC", "a1x", "a1", "", "", 2),
+            item("DOC", "a3", "a", "", "", 1),
+        ]
+        .concat();
+
+        let toc: Vec<Toc> = serde_yaml::from_str(&yml).unwrap();
+
+        let tree = Toc::into_tree(toc);
+
+        assert_eq!(
+            titles(&tree),
+            ["a(a1(a1x),a2,a3)", "b", "c"].map(String::from)
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    hash::{Hash, Hasher},
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use chrono::{DateTime, Local};
 
-use crate::time_serde;
+use crate::{handle_response, time_serde, Yuque, YuqueError, YuqueResponse};
 
 /// id - 用户编号
 /// type - 类型 [`User`  - 用户, Group - 团队]
@@ -13,7 +16,11 @@ use crate::time_serde;
 /// avatar_url - 头像 URL
 /// created_at - 创建时间
 /// updated_at - 更新时间
-#[derive(Deserialize, Debug)]
+///
+/// `User`、[`crate::DocListItem`]、[`crate::RepoListItem`] 都只按 `id` 计算哈希：
+/// `id` 在语雀中唯一且不会变化，同一对象在不同时间获取到的值（如 `updated_at` 不同）
+/// 会落在同一个桶里，可以直接作为 `HashMap`/`HashSet` 的键去重。相等性仍然比较全部字段。
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct User<'a> {
     pub id: i32,
     #[serde(rename = "type")]
@@ -27,6 +34,13 @@ pub struct User<'a> {
     pub updated_at: DateTime<Local>,
 }
 
+/// 只按 `id` 计算哈希，见 [`User`] 的说明
+impl<'a> Hash for User<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 /// id - 用户资料编号
 /// space_id - 企业空间编号
 /// account_id - 用户账户编号
@@ -41,7 +55,7 @@ pub struct User<'a> {
 /// description - 介绍
 /// created_at - 创建时间
 /// updated_at - 更新时间
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct UserDetail<'a> {
     pub id: i32,
     pub space_id: i32,
@@ -52,8 +66,11 @@ pub struct UserDetail<'a> {
     pub name: Cow<'a, str>,
     pub owner_id: Option<i32>,
     pub avatar_url: Cow<'a, str>,
+    #[serde(default)]
     pub books_count: i32,
+    #[serde(default)]
     pub public_books_count: i32,
+    #[serde(default)]
     pub members_count: i32,
     pub description: Option<Cow<'a, str>>,
     #[serde(with = "time_serde")]
@@ -61,3 +78,92 @@ pub struct UserDetail<'a> {
     #[serde(with = "time_serde")]
     pub updated_at: DateTime<Local>,
 }
+
+pub struct UsersClient {
+    pub(crate) client: Yuque,
+}
+
+/// `/user`，不与 `UsersClient` 的生命周期绑定，供 [`Yuque::verify`] 复用
+pub(crate) async fn current_user(
+    client: &Yuque,
+) -> Result<YuqueResponse<UserDetail<'static>>, YuqueError> {
+    let url = "/user".to_string();
+
+    let request = client.get(&url)?;
+
+    let response = client.execute(request).await?;
+
+    handle_response(response, url).await
+}
+
+impl UsersClient {
+    /// Get the authenticated user
+    /// 获取当前 token 对应的用户
+    ///
+    /// 也可以作为健康检查，在创建客户端后用它校验 token 是否有效：
+    /// token 无效时返回 `YuqueError::InvalidUserInfo`，需要更明确的错误时可以使用 [`Yuque::verify`]。
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let users = yuque.users();
+    ///
+    ///     let response = users.current().await?;
+    ///
+    ///     println!("{:?}", response);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn current(&self) -> Result<YuqueResponse<UserDetail<'_>>, YuqueError> {
+        current_user(&self.client).await
+    }
+
+    /// Get a user
+    /// 获取指定用户的公开资料
+    ///
+    /// 用户不存在时返回 `YuqueError::NotFound`。
+    ///
+    /// # Arguments
+    /// * `user` - 用户的 login 或编号
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use yuque_rust::Yuque;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let yuque = Yuque::builder()
+    ///                         .token("token".to_string())
+    ///                         .host("https://www.yuque.com".to_string())
+    ///                         .build()?;
+    ///
+    ///     let users = yuque.users();
+    ///
+    ///     let by_login = users.get("lzzzt").await?;
+    ///     let by_id = users.get(12345).await?;
+    ///
+    ///     println!("{:?} {:?}", by_login, by_id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get(
+        &self,
+        user: impl ToString,
+    ) -> Result<YuqueResponse<UserDetail<'_>>, YuqueError> {
+        let url = format!("/users/{}", user.to_string());
+
+        let request = self.client.get(&url)?;
+
+        let response = self.client.execute(request).await?;
+
+        handle_response(response, url).await
+    }
+}